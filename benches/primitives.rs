@@ -3,7 +3,10 @@
 extern crate bit_reverse;
 extern crate criterion;
 
-use bit_reverse::{BitwiseReverse, LookupReverse, ParallelReverse};
+use bit_reverse::{
+    BitwiseReverse, GfniReverse, LookupReverse, MultiplyReverse, NativeReverse,
+    NibbleLookupReverse, NibbleRegisterReverse, ParallelReverse, WideLookupReverse,
+};
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 
 static SEED: u64 = 0x0123456789ABCDEF;
@@ -17,9 +20,27 @@ fn bench_reverse(c: &mut Criterion) {
     group.bench_with_input(BenchmarkId::new("lookup", i), &i, |b, i| {
         b.iter(|| LookupReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("wide_lookup", i), &i, |b, i| {
+        b.iter(|| WideLookupReverse::swap_bits(*i))
+    });
     group.bench_with_input(BenchmarkId::new("parallel", i), &i, |b, i| {
         b.iter(|| ParallelReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("multiply", i), &i, |b, i| {
+        b.iter(|| MultiplyReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("gfni", i), &i, |b, i| {
+        b.iter(|| GfniReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("native", i), &i, |b, i| {
+        b.iter(|| NativeReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_lookup", i), &i, |b, i| {
+        b.iter(|| NibbleLookupReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_register", i), &i, |b, i| {
+        b.iter(|| NibbleRegisterReverse::swap_bits(*i))
+    });
     group.finish();
 
     let mut group = c.benchmark_group("i8_reverse");
@@ -30,9 +51,27 @@ fn bench_reverse(c: &mut Criterion) {
     group.bench_with_input(BenchmarkId::new("lookup", i), &i, |b, i| {
         b.iter(|| LookupReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("wide_lookup", i), &i, |b, i| {
+        b.iter(|| WideLookupReverse::swap_bits(*i))
+    });
     group.bench_with_input(BenchmarkId::new("parallel", i), &i, |b, i| {
         b.iter(|| ParallelReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("multiply", i), &i, |b, i| {
+        b.iter(|| MultiplyReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("gfni", i), &i, |b, i| {
+        b.iter(|| GfniReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("native", i), &i, |b, i| {
+        b.iter(|| NativeReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_lookup", i), &i, |b, i| {
+        b.iter(|| NibbleLookupReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_register", i), &i, |b, i| {
+        b.iter(|| NibbleRegisterReverse::swap_bits(*i))
+    });
     group.finish();
 
     let mut group = c.benchmark_group("u16_reverse");
@@ -43,9 +82,27 @@ fn bench_reverse(c: &mut Criterion) {
     group.bench_with_input(BenchmarkId::new("lookup", i), &i, |b, i| {
         b.iter(|| LookupReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("wide_lookup", i), &i, |b, i| {
+        b.iter(|| WideLookupReverse::swap_bits(*i))
+    });
     group.bench_with_input(BenchmarkId::new("parallel", i), &i, |b, i| {
         b.iter(|| ParallelReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("multiply", i), &i, |b, i| {
+        b.iter(|| MultiplyReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("gfni", i), &i, |b, i| {
+        b.iter(|| GfniReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("native", i), &i, |b, i| {
+        b.iter(|| NativeReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_lookup", i), &i, |b, i| {
+        b.iter(|| NibbleLookupReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_register", i), &i, |b, i| {
+        b.iter(|| NibbleRegisterReverse::swap_bits(*i))
+    });
     group.finish();
 
     let mut group = c.benchmark_group("i16_reverse");
@@ -56,9 +113,27 @@ fn bench_reverse(c: &mut Criterion) {
     group.bench_with_input(BenchmarkId::new("lookup", i), &i, |b, i| {
         b.iter(|| LookupReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("wide_lookup", i), &i, |b, i| {
+        b.iter(|| WideLookupReverse::swap_bits(*i))
+    });
     group.bench_with_input(BenchmarkId::new("parallel", i), &i, |b, i| {
         b.iter(|| ParallelReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("multiply", i), &i, |b, i| {
+        b.iter(|| MultiplyReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("gfni", i), &i, |b, i| {
+        b.iter(|| GfniReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("native", i), &i, |b, i| {
+        b.iter(|| NativeReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_lookup", i), &i, |b, i| {
+        b.iter(|| NibbleLookupReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_register", i), &i, |b, i| {
+        b.iter(|| NibbleRegisterReverse::swap_bits(*i))
+    });
     group.finish();
 
     let mut group = c.benchmark_group("u32_reverse");
@@ -69,9 +144,27 @@ fn bench_reverse(c: &mut Criterion) {
     group.bench_with_input(BenchmarkId::new("lookup", i), &i, |b, i| {
         b.iter(|| LookupReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("wide_lookup", i), &i, |b, i| {
+        b.iter(|| WideLookupReverse::swap_bits(*i))
+    });
     group.bench_with_input(BenchmarkId::new("parallel", i), &i, |b, i| {
         b.iter(|| ParallelReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("multiply", i), &i, |b, i| {
+        b.iter(|| MultiplyReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("gfni", i), &i, |b, i| {
+        b.iter(|| GfniReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("native", i), &i, |b, i| {
+        b.iter(|| NativeReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_lookup", i), &i, |b, i| {
+        b.iter(|| NibbleLookupReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_register", i), &i, |b, i| {
+        b.iter(|| NibbleRegisterReverse::swap_bits(*i))
+    });
     group.finish();
 
     let mut group = c.benchmark_group("i32_reverse");
@@ -82,22 +175,58 @@ fn bench_reverse(c: &mut Criterion) {
     group.bench_with_input(BenchmarkId::new("lookup", i), &i, |b, i| {
         b.iter(|| LookupReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("wide_lookup", i), &i, |b, i| {
+        b.iter(|| WideLookupReverse::swap_bits(*i))
+    });
     group.bench_with_input(BenchmarkId::new("parallel", i), &i, |b, i| {
         b.iter(|| ParallelReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("multiply", i), &i, |b, i| {
+        b.iter(|| MultiplyReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("gfni", i), &i, |b, i| {
+        b.iter(|| GfniReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("native", i), &i, |b, i| {
+        b.iter(|| NativeReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_lookup", i), &i, |b, i| {
+        b.iter(|| NibbleLookupReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_register", i), &i, |b, i| {
+        b.iter(|| NibbleRegisterReverse::swap_bits(*i))
+    });
     group.finish();
 
     let mut group = c.benchmark_group("u64_reverse");
-    let i = SEED as u64;
+    let i = SEED;
     group.bench_with_input(BenchmarkId::new("bitwise", i), &i, |b, i| {
         b.iter(|| BitwiseReverse::swap_bits(*i))
     });
     group.bench_with_input(BenchmarkId::new("lookup", i), &i, |b, i| {
         b.iter(|| LookupReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("wide_lookup", i), &i, |b, i| {
+        b.iter(|| WideLookupReverse::swap_bits(*i))
+    });
     group.bench_with_input(BenchmarkId::new("parallel", i), &i, |b, i| {
         b.iter(|| ParallelReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("multiply", i), &i, |b, i| {
+        b.iter(|| MultiplyReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("gfni", i), &i, |b, i| {
+        b.iter(|| GfniReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("native", i), &i, |b, i| {
+        b.iter(|| NativeReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_lookup", i), &i, |b, i| {
+        b.iter(|| NibbleLookupReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_register", i), &i, |b, i| {
+        b.iter(|| NibbleRegisterReverse::swap_bits(*i))
+    });
     group.finish();
 
     let mut group = c.benchmark_group("i64_reverse");
@@ -108,9 +237,27 @@ fn bench_reverse(c: &mut Criterion) {
     group.bench_with_input(BenchmarkId::new("lookup", i), &i, |b, i| {
         b.iter(|| LookupReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("wide_lookup", i), &i, |b, i| {
+        b.iter(|| WideLookupReverse::swap_bits(*i))
+    });
     group.bench_with_input(BenchmarkId::new("parallel", i), &i, |b, i| {
         b.iter(|| ParallelReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("multiply", i), &i, |b, i| {
+        b.iter(|| MultiplyReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("gfni", i), &i, |b, i| {
+        b.iter(|| GfniReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("native", i), &i, |b, i| {
+        b.iter(|| NativeReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_lookup", i), &i, |b, i| {
+        b.iter(|| NibbleLookupReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_register", i), &i, |b, i| {
+        b.iter(|| NibbleRegisterReverse::swap_bits(*i))
+    });
     group.finish();
 
     #[cfg(feature = "u128")]
@@ -123,9 +270,27 @@ fn bench_reverse(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::new("lookup", i), &i, |b, i| {
             b.iter(|| LookupReverse::swap_bits(*i))
         });
+        group.bench_with_input(BenchmarkId::new("wide_lookup", i), &i, |b, i| {
+            b.iter(|| WideLookupReverse::swap_bits(*i))
+        });
         group.bench_with_input(BenchmarkId::new("parallel", i), &i, |b, i| {
             b.iter(|| ParallelReverse::swap_bits(*i))
         });
+        group.bench_with_input(BenchmarkId::new("multiply", i), &i, |b, i| {
+            b.iter(|| MultiplyReverse::swap_bits(*i))
+        });
+        group.bench_with_input(BenchmarkId::new("gfni", i), &i, |b, i| {
+            b.iter(|| GfniReverse::swap_bits(*i))
+        });
+        group.bench_with_input(BenchmarkId::new("native", i), &i, |b, i| {
+            b.iter(|| NativeReverse::swap_bits(*i))
+        });
+        group.bench_with_input(BenchmarkId::new("nibble_lookup", i), &i, |b, i| {
+            b.iter(|| NibbleLookupReverse::swap_bits(*i))
+        });
+        group.bench_with_input(BenchmarkId::new("nibble_register", i), &i, |b, i| {
+            b.iter(|| NibbleRegisterReverse::swap_bits(*i))
+        });
         group.finish();
 
         let mut group = c.benchmark_group("i128_reverse");
@@ -136,9 +301,27 @@ fn bench_reverse(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::new("lookup", i), &i, |b, i| {
             b.iter(|| LookupReverse::swap_bits(*i))
         });
+        group.bench_with_input(BenchmarkId::new("wide_lookup", i), &i, |b, i| {
+            b.iter(|| WideLookupReverse::swap_bits(*i))
+        });
         group.bench_with_input(BenchmarkId::new("parallel", i), &i, |b, i| {
             b.iter(|| ParallelReverse::swap_bits(*i))
         });
+        group.bench_with_input(BenchmarkId::new("multiply", i), &i, |b, i| {
+            b.iter(|| MultiplyReverse::swap_bits(*i))
+        });
+        group.bench_with_input(BenchmarkId::new("gfni", i), &i, |b, i| {
+            b.iter(|| GfniReverse::swap_bits(*i))
+        });
+        group.bench_with_input(BenchmarkId::new("native", i), &i, |b, i| {
+            b.iter(|| NativeReverse::swap_bits(*i))
+        });
+        group.bench_with_input(BenchmarkId::new("nibble_lookup", i), &i, |b, i| {
+            b.iter(|| NibbleLookupReverse::swap_bits(*i))
+        });
+        group.bench_with_input(BenchmarkId::new("nibble_register", i), &i, |b, i| {
+            b.iter(|| NibbleRegisterReverse::swap_bits(*i))
+        });
         group.finish();
     }
 
@@ -150,9 +333,27 @@ fn bench_reverse(c: &mut Criterion) {
     group.bench_with_input(BenchmarkId::new("lookup", i), &i, |b, i| {
         b.iter(|| LookupReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("wide_lookup", i), &i, |b, i| {
+        b.iter(|| WideLookupReverse::swap_bits(*i))
+    });
     group.bench_with_input(BenchmarkId::new("parallel", i), &i, |b, i| {
         b.iter(|| ParallelReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("multiply", i), &i, |b, i| {
+        b.iter(|| MultiplyReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("gfni", i), &i, |b, i| {
+        b.iter(|| GfniReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("native", i), &i, |b, i| {
+        b.iter(|| NativeReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_lookup", i), &i, |b, i| {
+        b.iter(|| NibbleLookupReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_register", i), &i, |b, i| {
+        b.iter(|| NibbleRegisterReverse::swap_bits(*i))
+    });
     group.finish();
 
     let mut group = c.benchmark_group("isize_reverse");
@@ -163,9 +364,27 @@ fn bench_reverse(c: &mut Criterion) {
     group.bench_with_input(BenchmarkId::new("lookup", i), &i, |b, i| {
         b.iter(|| LookupReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("wide_lookup", i), &i, |b, i| {
+        b.iter(|| WideLookupReverse::swap_bits(*i))
+    });
     group.bench_with_input(BenchmarkId::new("parallel", i), &i, |b, i| {
         b.iter(|| ParallelReverse::swap_bits(*i))
     });
+    group.bench_with_input(BenchmarkId::new("multiply", i), &i, |b, i| {
+        b.iter(|| MultiplyReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("gfni", i), &i, |b, i| {
+        b.iter(|| GfniReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("native", i), &i, |b, i| {
+        b.iter(|| NativeReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_lookup", i), &i, |b, i| {
+        b.iter(|| NibbleLookupReverse::swap_bits(*i))
+    });
+    group.bench_with_input(BenchmarkId::new("nibble_register", i), &i, |b, i| {
+        b.iter(|| NibbleRegisterReverse::swap_bits(*i))
+    });
     group.finish();
 }
 
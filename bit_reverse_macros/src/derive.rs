@@ -0,0 +1,232 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Index, LitInt, Member, Type};
+
+// The algorithm traits implemented for derived types. Any of them can be the default algorithm,
+// so derived types always implement `bit_reverse::BitReverse`.
+const TRAITS: [&str; 3] = ["BitwiseReverse", "LookupReverse", "ParallelReverse"];
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "`BitReverse` can only be derived for structs",
+            ))
+        }
+    };
+
+    let members: Vec<(Member, &Type, Option<u32>)> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let member = match field.ident {
+                Some(ref ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index::from(i)),
+            };
+            Ok((member, &field.ty, bits_attr(field)?))
+        })
+        .collect::<syn::Result<_>>()?;
+
+    let bitfield = members.iter().any(|&(_, _, bits)| bits.is_some());
+    if bitfield {
+        bitfield_impls(&input, &members)
+    } else if members.len() == 1 {
+        Ok(newtype_impls(&input, &members[0].0, members[0].1))
+    } else {
+        Err(Error::new(
+            input.ident.span(),
+            "`BitReverse` needs a single field, or a `#[bits(N)]` width on every field",
+        ))
+    }
+}
+
+// Reads the width out of a field's `#[bits(N)]` attribute.
+fn bits_attr(field: &syn::Field) -> syn::Result<Option<u32>> {
+    let mut bits = None;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("bits")) {
+        if bits.is_some() {
+            return Err(Error::new_spanned(attr, "duplicate `#[bits(N)]` attribute"));
+        }
+        let lit: LitInt = attr.parse_args()?;
+        let n = lit.base10_parse::<u32>()?;
+        if n == 0 || n > 128 {
+            return Err(Error::new(
+                lit.span(),
+                "field widths must be between 1 and 128 bits",
+            ));
+        }
+        if let Some((width, _)) = primitive(&field.ty) {
+            if n > width {
+                return Err(Error::new(
+                    lit.span(),
+                    format!("a {} bit field doesn't fit in its type", n),
+                ));
+            }
+        }
+        bits = Some(n);
+    }
+    Ok(bits)
+}
+
+// The width and signedness of primitive integer types, so too wide fields can be caught here
+// rather than silently truncated.
+fn primitive(ty: &Type) -> Option<(u32, bool)> {
+    let ident = match *ty {
+        Type::Path(ref path) if path.qself.is_none() => path.path.get_ident()?.to_string(),
+        _ => return None,
+    };
+    match ident.as_str() {
+        "u8" => Some((8, false)),
+        "u16" => Some((16, false)),
+        "u32" => Some((32, false)),
+        "u64" => Some((64, false)),
+        "u128" => Some((128, false)),
+        "i8" => Some((8, true)),
+        "i16" => Some((16, true)),
+        "i32" => Some((32, true)),
+        "i64" => Some((64, true)),
+        "i128" => Some((128, true)),
+        _ => None,
+    }
+}
+
+// Newtypes delegate to the field's own implementation of each algorithm.
+fn newtype_impls(input: &DeriveInput, member: &Member, ty: &Type) -> TokenStream {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates = where_clause.map(|w| &w.predicates);
+    let impls = TRAITS.iter().map(|t| {
+        let tr = syn::Ident::new(t, Span::call_site());
+        quote! {
+            impl #impl_generics ::bit_reverse::#tr for #name #ty_generics
+            where
+                #ty: ::bit_reverse::#tr,
+                #predicates
+            {
+                #[inline]
+                fn swap_bits(self) -> Self {
+                    let mut value = self;
+                    value.#member = ::bit_reverse::#tr::swap_bits(value.#member);
+                    value
+                }
+
+                #[inline]
+                fn swap_low_bits(self, bits: u32) -> Self {
+                    let mut value = self;
+                    value.#member = ::bit_reverse::#tr::swap_low_bits(value.#member, bits);
+                    value
+                }
+            }
+        }
+    });
+    quote!(#(#impls)*)
+}
+
+// Bitfields pack their fields LSB-first into the smallest integer that holds them, the first field
+// in the lowest bits, reverse the packed value, and unpack it with the same layout. Fields that
+// straddle the middle therefore trade places, and a field's bits can move into its neighbours.
+fn bitfield_impls(
+    input: &DeriveInput,
+    members: &[(Member, &Type, Option<u32>)],
+) -> syn::Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "`BitReverse` can't be derived for generic bitfields",
+        ));
+    }
+    let mut layout = Vec::new();
+    let mut offset = 0u32;
+    for &(ref member, ty, bits) in members {
+        let bits = match bits {
+            Some(bits) => bits,
+            None => {
+                return Err(Error::new(
+                    member_span(member),
+                    "every field of a bitfield needs a `#[bits(N)]` width",
+                ))
+            }
+        };
+        layout.push((member, ty, offset, bits));
+        offset += bits;
+    }
+    let width = offset;
+    let packed = match width {
+        0..=8 => quote!(u8),
+        9..=16 => quote!(u16),
+        17..=32 => quote!(u32),
+        33..=64 => quote!(u64),
+        65..=128 => quote!(u128),
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                format!("a {} bit bitfield doesn't fit in a `u128`", width),
+            ))
+        }
+    };
+
+    let pack: Vec<TokenStream> = layout
+        .iter()
+        .map(|&(member, _, offset, bits)| {
+            let mask = mask(bits);
+            quote!(| (((self.#member as u128) & #mask) << #offset))
+        })
+        .collect();
+    let unpack: Vec<TokenStream> = layout
+        .iter()
+        .map(|&(member, ty, offset, bits)| {
+            match primitive(ty) {
+                // Signed fields are sign extended from their top bit, so they survive a round trip.
+                Some((_, true)) => {
+                    let up = 128 - offset - bits;
+                    let down = 128 - bits;
+                    quote!(#member: ((((packed as u128) << #up) as i128) >> #down) as #ty)
+                }
+                _ => {
+                    let mask = mask(bits);
+                    quote!(#member: (((packed as u128) >> #offset) & #mask) as #ty)
+                }
+            }
+        })
+        .collect();
+
+    let name = &input.ident;
+    let impls = TRAITS.iter().map(|t| {
+        let tr = syn::Ident::new(t, Span::call_site());
+        quote! {
+            impl ::bit_reverse::#tr for #name {
+                #[inline]
+                fn swap_bits(self) -> Self {
+                    ::bit_reverse::#tr::swap_low_bits(self, #width)
+                }
+
+                #[inline]
+                fn swap_low_bits(self, bits: u32) -> Self {
+                    assert!(
+                        bits <= #width,
+                        "cannot reverse more bits than the type holds"
+                    );
+                    let packed = (0u128 #(#pack)*) as #packed;
+                    let packed = ::bit_reverse::#tr::swap_low_bits(packed, bits);
+                    Self { #(#unpack),* }
+                }
+            }
+        }
+    });
+    Ok(quote!(#(#impls)*))
+}
+
+fn mask(bits: u32) -> TokenStream {
+    let mask = u128::MAX >> (128 - bits);
+    let lit = LitInt::new(&format!("{:#x}u128", mask), Span::call_site());
+    quote!(#lit)
+}
+
+fn member_span(member: &Member) -> Span {
+    match *member {
+        Member::Named(ref ident) => ident.span(),
+        Member::Unnamed(ref index) => index.span,
+    }
+}
@@ -0,0 +1,267 @@
+//! Procedural macros for the `bit_reverse` crate.
+//!
+//! `bitrev!` reverses an integer literal at compile time, so LSB-first constants can be written
+//! next to the MSB-first form a datasheet gives them in. The reversal is done with
+//! `bit_reverse::parallel`, so it always agrees with the runtime algorithms.
+//!
+//! A suffixed literal is reversed across its type, and the result keeps the suffix.
+//!
+//! ```
+//! use bit_reverse_macros::bitrev;
+//!
+//! assert_eq!(bitrev!(0xA0u8), 0x05u8);
+//! assert_eq!(bitrev!(0x04C11DB7u32), 0xEDB88320u32);
+//! assert_eq!(bitrev!(-128i8), 1i8);
+//! ```
+//!
+//! `width = N` reverses only the lowest N bits instead, and is required for unsuffixed literals.
+//!
+//! ```
+//! use bit_reverse_macros::bitrev;
+//!
+//! const LSB_FIRST: u16 = bitrev!(0x1021, width = 16);
+//! assert_eq!(LSB_FIRST, 0x8408);
+//! assert_eq!(bitrev!(0b1011, width = 4), 0b1101);
+//! assert_eq!(bitrev!(0b1011u8, width = 4), 0b1101u8);
+//! ```
+//!
+//! Literals that don't fit in the width, and widths that don't fit in the type, are compile
+//! errors that point at the offending token.
+//!
+//! ```compile_fail
+//! use bit_reverse_macros::bitrev;
+//!
+//! let _ = bitrev!(0x1F, width = 4);
+//! ```
+//!
+//! ```compile_fail
+//! use bit_reverse_macros::bitrev;
+//!
+//! let _ = bitrev!(0x1u8, width = 12);
+//! ```
+//!
+//! ```compile_fail
+//! use bit_reverse_macros::bitrev;
+//!
+//! let _ = bitrev!(0x1F);
+//! ```
+//!
+//! `#[derive(BitReverse)]` implements `BitwiseReverse`, `LookupReverse` and `ParallelReverse` for
+//! a struct, so it can be reversed with any of them and passed to code bounded on
+//! `bit_reverse::BitReverse`. A struct with a single field, such as a register newtype, delegates
+//! to the field.
+//!
+//! ```
+//! use bit_reverse::{LookupReverse, ParallelReverse};
+//! use bit_reverse_macros::BitReverse;
+//!
+//! #[derive(BitReverse, Clone, Copy, Debug, PartialEq)]
+//! #[repr(transparent)]
+//! struct Control(u32);
+//!
+//! assert_eq!(ParallelReverse::swap_bits(Control(1)), Control(0x8000_0000));
+//! assert_eq!(LookupReverse::swap_low_bits(Control(1), 4), Control(0b1000));
+//! ```
+//!
+//! A struct whose integer fields all have a `#[bits(N)]` width is a bitfield. Its fields are
+//! packed LSB-first, the first field in the lowest bits, and the whole packed value is reversed,
+//! so the fields trade places as well as having their bits reversed. Bitfields wider than 64
+//! bits need the `u128` feature of `bit_reverse`.
+//!
+//! ```
+//! use bit_reverse::ParallelReverse;
+//! use bit_reverse_macros::BitReverse;
+//!
+//! #[derive(BitReverse, Clone, Copy, Debug, PartialEq)]
+//! struct Header {
+//!     #[bits(3)]
+//!     kind: u8,
+//!     #[bits(5)]
+//!     length: u8,
+//! }
+//!
+//! // 0b10110_001 reversed is 0b100_01101.
+//! let header = Header { kind: 0b001, length: 0b10110 };
+//! assert_eq!(header.swap_bits(), Header { kind: 0b101, length: 0b10001 });
+//! ```
+//!
+//! ```compile_fail
+//! use bit_reverse_macros::BitReverse;
+//!
+//! #[derive(BitReverse)]
+//! struct Header {
+//!     #[bits(3)]
+//!     kind: u8,
+//!     length: u8,
+//! }
+//! ```
+//!
+//! This crate depends on `bit_reverse`, so `bit_reverse` can't re-export it. Depend on both.
+
+extern crate proc_macro;
+
+mod derive;
+
+use bit_reverse::parallel::reverse_u64;
+use proc_macro::TokenStream;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, DeriveInput, Error, Ident, LitInt, Token};
+
+/// Reverses an integer literal at compile time. See the crate documentation for examples.
+#[proc_macro]
+pub fn bitrev(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as BitrevInput);
+    match input.expand() {
+        Ok(lit) => quote::quote!(#lit).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Implements the bitwise, lookup and parallel algorithm traits of `bit_reverse`, and so
+/// `bit_reverse::BitReverse`, for a struct. See the crate documentation for examples.
+#[proc_macro_derive(BitReverse, attributes(bits))]
+pub fn derive_bit_reverse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive::expand(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+struct BitrevInput {
+    negative: Option<Token![-]>,
+    lit: LitInt,
+    width: Option<LitInt>,
+}
+
+impl Parse for BitrevInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let negative = input.parse()?;
+        let lit = input.parse()?;
+        let mut width = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "width" {
+                return Err(Error::new(key.span(), "expected `width = N`"));
+            }
+            input.parse::<Token![=]>()?;
+            width = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(BitrevInput {
+            negative,
+            lit,
+            width,
+        })
+    }
+}
+
+impl BitrevInput {
+    fn expand(&self) -> syn::Result<LitInt> {
+        let lit = &self.lit;
+        let suffix = lit.suffix();
+        let (type_bits, signed) = match suffix {
+            "" => (None, false),
+            "u8" => (Some(8), false),
+            "u16" => (Some(16), false),
+            "u32" => (Some(32), false),
+            "u64" => (Some(64), false),
+            "u128" => (Some(128), false),
+            "i8" => (Some(8), true),
+            "i16" => (Some(16), true),
+            "i32" => (Some(32), true),
+            "i64" => (Some(64), true),
+            "i128" => (Some(128), true),
+            "usize" | "isize" => {
+                return Err(Error::new(
+                    lit.span(),
+                    format!(
+                        "the width of `{}` depends on the target, use a fixed width type",
+                        suffix
+                    ),
+                ))
+            }
+            _ => {
+                return Err(Error::new(
+                    lit.span(),
+                    format!("unsupported suffix `{}`", suffix),
+                ))
+            }
+        };
+
+        let width = match (&self.width, type_bits) {
+            (Some(width), _) => {
+                let bits = width.base10_parse::<u32>()?;
+                if bits > type_bits.unwrap_or(128) {
+                    let ty = if suffix.is_empty() { "u128" } else { suffix };
+                    return Err(Error::new(
+                        width.span(),
+                        format!("cannot reverse {} bits of a `{}`", bits, ty),
+                    ));
+                }
+                bits
+            }
+            (None, Some(bits)) => bits,
+            (None, None) => {
+                return Err(Error::new(
+                    lit.span(),
+                    "unsuffixed literals need a `width = N` to know how many bits to reverse",
+                ))
+            }
+        };
+
+        let magnitude = lit.base10_parse::<u128>()?;
+        let value = match self.negative {
+            None => {
+                if width < 128 && magnitude >> width != 0 {
+                    return Err(Error::new(
+                        lit.span(),
+                        format!("`{}` doesn't fit in {} bits", lit, width),
+                    ));
+                }
+                magnitude
+            }
+            Some(minus) => {
+                // Negative literals are reversed as their two's complement across the whole type.
+                if !signed || Some(width) != type_bits {
+                    return Err(Error::new(
+                        minus.span,
+                        "only negative literals with a signed suffix and no narrower width can be \
+                         reversed",
+                    ));
+                }
+                if magnitude > 1 << (width - 1) {
+                    return Err(Error::new(
+                        lit.span(),
+                        format!("`-{}` doesn't fit in a `{}`", lit, suffix),
+                    ));
+                }
+                magnitude.wrapping_neg() & (u128::MAX >> (128 - width))
+            }
+        };
+
+        let reversed = reverse_low_bits(value, width);
+        let repr = match type_bits {
+            // Signed results are written in decimal, so a set sign bit becomes a negative literal
+            // rather than an out of range one.
+            Some(bits) if signed => {
+                let shift = 128 - bits;
+                format!("{}{}", ((reversed << shift) as i128) >> shift, suffix)
+            }
+            _ => format!("{:#x}{}", reversed, suffix),
+        };
+        Ok(LitInt::new(&repr, lit.span()))
+    }
+}
+
+// Reverses the lowest `width` bits of `value`. The halves are reversed as `u64`s so that
+// `bit_reverse` doesn't need its `u128` feature, which would otherwise be enabled for every crate
+// in the workspace.
+fn reverse_low_bits(value: u128, width: u32) -> u128 {
+    let reversed =
+        (reverse_u64(value as u64) as u128) << 64 | reverse_u64((value >> 64) as u64) as u128;
+    if width == 0 {
+        0
+    } else {
+        reversed >> (128 - width)
+    }
+}
@@ -0,0 +1,38 @@
+use bit_reverse::ParallelReverse;
+use bit_reverse_macros::bitrev;
+
+#[test]
+fn whole_types() {
+    assert_eq!(bitrev!(0xA0u8), 0x05u8);
+    assert_eq!(bitrev!(0xABCDu16), 0xB3D5u16);
+    assert_eq!(bitrev!(0x04C11DB7u32), 0xEDB88320u32);
+    assert_eq!(bitrev!(0x0123456789ABCDEFu64), 0xF7B3D591E6A2C480u64);
+    assert_eq!(
+        bitrev!(0x0123456789ABCDEF0123456789ABCDEFu128),
+        0xF7B3D591E6A2C480F7B3D591E6A2C480u128
+    );
+}
+
+#[test]
+fn signed_types() {
+    assert_eq!(bitrev!(1i8), -128i8);
+    assert_eq!(bitrev!(-128i8), 1i8);
+    assert_eq!(bitrev!(-1i32), -1i32);
+    assert_eq!(bitrev!(0x1234i16), 0x1234i16.swap_bits());
+    assert_eq!(bitrev!(-2i64), (-2i64).swap_bits());
+}
+
+#[test]
+fn low_bits() {
+    assert_eq!(bitrev!(0b1011, width = 4), 0b1101);
+    assert_eq!(bitrev!(0b1011u8, width = 4), 0b1101u8);
+    assert_eq!(bitrev!(0x1021u32, width = 16,), 0x8408u32);
+    assert_eq!(bitrev!(0, width = 0), 0);
+    assert_eq!(bitrev!(0x1u128, width = 128), 1u128 << 127);
+}
+
+#[test]
+fn in_constants() {
+    const POLYS: [u32; 2] = [bitrev!(0x04C11DB7u32), bitrev!(0x1EDC6F41u32)];
+    assert_eq!(POLYS, [0xEDB88320, 0x82F63B78]);
+}
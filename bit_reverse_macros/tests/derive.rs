@@ -0,0 +1,128 @@
+use bit_reverse::{reverse, reverse_low_bits, BitReverse, Lookup, ParallelReverse};
+use bit_reverse_macros::BitReverse;
+
+#[derive(BitReverse, Clone, Copy, Debug, PartialEq)]
+#[repr(transparent)]
+struct Register(u32);
+
+#[derive(BitReverse, Clone, Copy, Debug, PartialEq)]
+struct Named {
+    bits: u16,
+}
+
+#[derive(BitReverse, Clone, Copy, Debug, PartialEq)]
+struct Wrapper<T>(T);
+
+#[derive(BitReverse, Clone, Copy, Debug, PartialEq)]
+struct Header {
+    #[bits(3)]
+    kind: u8,
+    #[bits(5)]
+    length: u8,
+}
+
+#[derive(BitReverse, Clone, Copy, Debug, PartialEq)]
+struct Tuple(#[bits(4)] u8, #[bits(8)] u16, #[bits(4)] u8);
+
+#[derive(BitReverse, Clone, Copy, Debug, PartialEq)]
+struct Signed {
+    #[bits(4)]
+    low: i8,
+    #[bits(4)]
+    high: i8,
+}
+
+#[derive(BitReverse, Clone, Copy, Debug, PartialEq)]
+struct Wide {
+    #[bits(20)]
+    low: u32,
+    #[bits(20)]
+    high: u32,
+}
+
+#[test]
+fn newtypes_delegate() {
+    assert_eq!(Register(0x04C11DB7).swap_bits(), Register(0xEDB88320));
+    assert_eq!(Named { bits: 0x1021 }.swap_bits(), Named { bits: 0x8408 });
+    assert_eq!(Wrapper(0xA0u8).swap_bits(), Wrapper(0x05u8));
+    assert_eq!(
+        Register(0x1021).reverse_bits_with::<Lookup>(),
+        Register(0x8408_0000)
+    );
+    assert_eq!(reverse_low_bits(Register(0x1021), 16), Register(0x8408));
+}
+
+#[test]
+fn bitfields_reverse_the_packed_value() {
+    // Packed as 0b10110_001, which reverses to 0b100_01101.
+    let header = Header {
+        kind: 0b001,
+        length: 0b10110,
+    };
+    assert_eq!(
+        header.swap_bits(),
+        Header {
+            kind: 0b101,
+            length: 0b10001,
+        }
+    );
+    assert_eq!(header.swap_bits().swap_bits(), header);
+    assert_eq!(reverse(header), header.swap_bits());
+
+    // Symmetric layouts swap the outer fields and reverse each field in place.
+    assert_eq!(
+        Tuple(0b0001, 0b1100_0000, 0b0110).swap_bits(),
+        Tuple(0b0110, 0b0000_0011, 0b1000)
+    );
+}
+
+#[test]
+fn bitfield_low_bits() {
+    // Only the low 4 bits, 0b1_011, are reversed and the rest are cleared.
+    let header = Header {
+        kind: 0b011,
+        length: 0b11111,
+    };
+    assert_eq!(
+        header.swap_low_bits(4),
+        Header {
+            kind: 0b101,
+            length: 0b00001,
+        }
+    );
+}
+
+#[test]
+fn signed_fields_round_trip() {
+    let signed = Signed { low: -1, high: 2 };
+    assert_eq!(signed.swap_bits(), Signed { low: 4, high: -1 });
+    assert_eq!(signed.swap_bits().swap_bits(), signed);
+}
+
+#[test]
+fn wide_bitfields() {
+    let wide = Wide {
+        low: 1,
+        high: 0x8_0000,
+    };
+    assert_eq!(
+        wide.swap_bits(),
+        Wide {
+            low: 1,
+            high: 0x8_0000,
+        }
+    );
+    assert_eq!(
+        Wide { low: 1, high: 0 }.swap_bits(),
+        Wide {
+            low: 0,
+            high: 0x8_0000,
+        }
+    );
+}
+
+#[test]
+#[should_panic]
+fn bitfield_low_bits_too_wide() {
+    Header { kind: 0, length: 0 }.swap_low_bits(9);
+}
@@ -0,0 +1,297 @@
+use std::env;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+/// The algorithms `AutoReverse` can dispatch to. `ConstTimeReverse` isn't one of them, since it is
+/// chosen for its timing rather than its speed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// `BitwiseReverse`.
+    Bitwise,
+    /// `LookupReverse`.
+    Lookup,
+    /// `ParallelReverse`.
+    Parallel,
+    /// `NativeReverse`.
+    Native,
+    /// `WideLookupReverse`.
+    WideLookup,
+    /// `MultiplyReverse`.
+    Multiply,
+    /// `GfniReverse`.
+    Gfni,
+    /// `NibbleLookupReverse`.
+    NibbleLookup,
+    /// `NibbleRegisterReverse`.
+    NibbleRegister,
+}
+
+impl Backend {
+    /// The number of backends.
+    pub const COUNT: usize = 9;
+
+    /// Every backend, in the order `AutoReverse` measures them.
+    pub const ALL: [Backend; Backend::COUNT] = [
+        Backend::Bitwise,
+        Backend::Lookup,
+        Backend::Parallel,
+        Backend::Native,
+        Backend::WideLookup,
+        Backend::Multiply,
+        Backend::Gfni,
+        Backend::NibbleLookup,
+        Backend::NibbleRegister,
+    ];
+
+    /// The lowercase name of the backend, as accepted by `from_name`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::Bitwise => "bitwise",
+            Backend::Lookup => "lookup",
+            Backend::Parallel => "parallel",
+            Backend::Native => "native",
+            Backend::WideLookup => "wide_lookup",
+            Backend::Multiply => "multiply",
+            Backend::Gfni => "gfni",
+            Backend::NibbleLookup => "nibble_lookup",
+            Backend::NibbleRegister => "nibble_register",
+        }
+    }
+
+    /// Returns the backend called `name`, ignoring case.
+    pub fn from_name(name: &str) -> Option<Backend> {
+        Backend::ALL
+            .iter()
+            .cloned()
+            .find(|backend| backend.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// The environment variable that pins every type to one backend instead of measuring them.
+pub const BACKEND_VAR: &str = "BIT_REVERSE_BACKEND";
+
+/// Bit reversal that measures every backend on the current CPU the first time a type is used, and
+/// then dispatches to the fastest one through a function pointer.
+///
+/// Each unsigned type is measured separately, and signed types share the choice made for the
+/// unsigned type of the same width. The choice can be pinned per type with `pin_backend`, or for
+/// every type by setting the `BIT_REVERSE_BACKEND` environment variable to the name of a backend
+/// before first use.
+///
+/// # Timing
+/// Not constant time, since the chosen backend may be `BitwiseReverse` or `LookupReverse`.
+///
+/// ```
+/// use bit_reverse::{AutoReverse, Backend};
+///
+/// assert_eq!(0xA0u8.swap_bits(), 0x05u8);
+///
+/// u32::pin_backend(Backend::Lookup);
+/// assert_eq!(u32::backend(), Backend::Lookup);
+/// ```
+pub trait AutoReverse: Sized {
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
+    fn swap_bits(self) -> Self;
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    fn swap_low_bits(self, bits: u32) -> Self;
+
+    /// The backend this type dispatches to, choosing one first if that hasn't happened yet.
+    fn backend() -> Backend;
+
+    /// Makes this type dispatch to `backend` from now on.
+    fn pin_backend(backend: Backend);
+
+    /// Chooses the backend again, as on first use, and returns it.
+    fn retune() -> Backend;
+}
+
+// Each slot holds zero until its type has a backend, and the backend's index plus one after.
+static SLOTS: [AtomicU8; 6] = [
+    AtomicU8::new(0),
+    AtomicU8::new(0),
+    AtomicU8::new(0),
+    AtomicU8::new(0),
+    AtomicU8::new(0),
+    AtomicU8::new(0),
+];
+
+// The number of values reversed per measurement, and the number of measurements per backend.
+const SAMPLES: usize = 1024;
+const ROUNDS: usize = 5;
+
+#[inline]
+fn load(slot: usize) -> Option<Backend> {
+    match SLOTS[slot].load(Ordering::Relaxed) {
+        0 => None,
+        state => Some(Backend::ALL[state as usize - 1]),
+    }
+}
+
+fn store(slot: usize, backend: Backend) {
+    SLOTS[slot].store(backend as u8 + 1, Ordering::Relaxed);
+}
+
+// Returns the backend whose fastest round over `samples` took the least time. Keeping only each
+// backend's best round filters out preemption and other noise.
+fn fastest<T: Copy>(fns: &[fn(T) -> T; Backend::COUNT], samples: &[T]) -> Backend {
+    let mut best = (Duration::MAX, Backend::Parallel);
+    for _ in 0..ROUNDS {
+        for (&backend, &f) in Backend::ALL.iter().zip(fns.iter()) {
+            let start = Instant::now();
+            for &v in samples {
+                black_box(f(black_box(v)));
+            }
+            let elapsed = start.elapsed();
+            if elapsed < best.0 {
+                best = (elapsed, backend);
+            }
+        }
+    }
+    best.1
+}
+
+fn tune<T: Copy>(slot: usize, fns: &[fn(T) -> T; Backend::COUNT], samples: &[T]) -> Backend {
+    let backend = env::var(BACKEND_VAR)
+        .ok()
+        .and_then(|name| Backend::from_name(&name))
+        .unwrap_or_else(|| fastest(fns, samples));
+    store(slot, backend);
+    backend
+}
+
+// Every backend's functions for one type, indexed by `Backend`.
+trait Dispatch: Sized {
+    const SWAP: [fn(Self) -> Self; Backend::COUNT];
+    const SWAP_LOW: [fn(Self, u32) -> Self; Backend::COUNT];
+}
+
+macro_rules! doit_auto { ($($ty:ty => $slot:expr),*) => ($(
+    impl Dispatch for $ty {
+        const SWAP: [fn($ty) -> $ty; Backend::COUNT] = [
+            <$ty as ::BitwiseReverse>::swap_bits,
+            <$ty as ::LookupReverse>::swap_bits,
+            <$ty as ::ParallelReverse>::swap_bits,
+            <$ty as ::NativeReverse>::swap_bits,
+            <$ty as ::WideLookupReverse>::swap_bits,
+            <$ty as ::MultiplyReverse>::swap_bits,
+            <$ty as ::GfniReverse>::swap_bits,
+            <$ty as ::NibbleLookupReverse>::swap_bits,
+            <$ty as ::NibbleRegisterReverse>::swap_bits,
+        ];
+        const SWAP_LOW: [fn($ty, u32) -> $ty; Backend::COUNT] = [
+            <$ty as ::BitwiseReverse>::swap_low_bits,
+            <$ty as ::LookupReverse>::swap_low_bits,
+            <$ty as ::ParallelReverse>::swap_low_bits,
+            <$ty as ::NativeReverse>::swap_low_bits,
+            <$ty as ::WideLookupReverse>::swap_low_bits,
+            <$ty as ::MultiplyReverse>::swap_low_bits,
+            <$ty as ::GfniReverse>::swap_low_bits,
+            <$ty as ::NibbleLookupReverse>::swap_low_bits,
+            <$ty as ::NibbleRegisterReverse>::swap_low_bits,
+        ];
+    }
+
+    impl AutoReverse for $ty {
+        #[inline]
+        fn swap_bits(self) -> $ty {
+            <$ty as Dispatch>::SWAP[<$ty as AutoReverse>::backend() as usize](self)
+        }
+
+        #[inline]
+        fn swap_low_bits(self, bits: u32) -> $ty {
+            <$ty as Dispatch>::SWAP_LOW[<$ty as AutoReverse>::backend() as usize](self, bits)
+        }
+
+        #[inline]
+        fn backend() -> Backend {
+            load($slot).unwrap_or_else(<$ty as AutoReverse>::retune)
+        }
+
+        fn pin_backend(backend: Backend) {
+            store($slot, backend);
+        }
+
+        fn retune() -> Backend {
+            // An xorshift sequence, so no backend benefits from repeated inputs.
+            let mut samples = [0 as $ty; SAMPLES];
+            for (sample, x) in samples.iter_mut().zip(::xorshift()) {
+                *sample = x as $ty;
+            }
+            tune($slot, &<$ty as Dispatch>::SWAP, &samples)
+        }
+    })*)
+}
+
+macro_rules! doit_auto_signed { ($($ty:ty => $unsigned:ty),*) => ($(
+    impl AutoReverse for $ty {
+        #[inline]
+        fn swap_bits(self) -> $ty {
+            AutoReverse::swap_bits(self as $unsigned) as $ty
+        }
+
+        #[inline]
+        fn swap_low_bits(self, bits: u32) -> $ty {
+            AutoReverse::swap_low_bits(self as $unsigned, bits) as $ty
+        }
+
+        #[inline]
+        fn backend() -> Backend {
+            <$unsigned as AutoReverse>::backend()
+        }
+
+        fn pin_backend(backend: Backend) {
+            <$unsigned as AutoReverse>::pin_backend(backend)
+        }
+
+        fn retune() -> Backend {
+            <$unsigned as AutoReverse>::retune()
+        }
+    })*)
+}
+
+doit_auto!(u8 => 0, u16 => 1, u32 => 2, u64 => 3, usize => 4);
+doit_auto_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize);
+#[cfg(feature = "u128")]
+doit_auto!(u128 => 5);
+#[cfg(feature = "u128")]
+doit_auto_signed!(i128 => u128);
+
+test_suite!();
+
+#[test]
+fn backend_names() {
+    for &backend in Backend::ALL.iter() {
+        assert_eq!(Backend::from_name(backend.name()), Some(backend));
+    }
+    assert_eq!(Backend::from_name(" Lookup\n"), Some(Backend::Lookup));
+    assert_eq!(
+        Backend::from_name("nibble_register"),
+        Some(Backend::NibbleRegister)
+    );
+    assert_eq!(Backend::from_name("simd"), None);
+}
+
+#[test]
+fn pinned_backends_agree() {
+    // Other tests may tune u16 at the same time, so only the results are checked here.
+    for &backend in Backend::ALL.iter() {
+        i16::pin_backend(backend);
+        assert_eq!(0xABCDu16.swap_bits(), 0xB3D5);
+        assert_eq!(0xABCDi16.swap_bits(), 0xB3D5i16);
+        assert_eq!(0x1021u16.swap_low_bits(13), 0x1081);
+    }
+    // Leave u16 with a measured backend for the rest of the suite.
+    u16::retune();
+}
+
+#[test]
+fn retune_picks_a_backend() {
+    assert!(Backend::ALL.contains(&u64::retune()));
+    assert_eq!(0x0123456789ABCDEFu64.swap_bits(), 0xF7B3D591E6A2C480);
+}
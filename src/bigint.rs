@@ -0,0 +1,146 @@
+//! Bit reversal of arbitrary-precision integers from `num_bigint`.
+//!
+//! A big integer has no natural width, since its leading zeros aren't stored, so it is always
+//! reversed to an explicit number of bits.
+//!
+//! ```
+//! extern crate bit_reverse;
+//! extern crate num_bigint;
+//!
+//! use bit_reverse::BigReverse;
+//! use num_bigint::{BigInt, BigUint};
+//!
+//! # fn main() {
+//! let one = BigUint::from(1u8);
+//! assert_eq!(one.swap_low_bits(200), BigUint::from(1u8) << 199u32);
+//!
+//! // Signed values are reversed as their two's complement in that many bits.
+//! assert_eq!(BigInt::from(1).swap_low_bits(8), BigInt::from(-128));
+//! # }
+//! ```
+
+use num_bigint::{BigInt, BigUint, Sign};
+use ParallelReverse;
+
+/// Computes bit reversal of big integers by reversing the order of their limbs and the bits of
+/// each limb with `ParallelReverse`. The limbs are `u64` on 64-bit targets and `u32` elsewhere.
+///
+/// # Timing
+/// Not constant time. The running time depends on the size of the value as well as the width.
+pub trait BigReverse {
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are dropped. A `BigInt` is reversed as its `bits` wide two's complement, and the result is
+    /// read back as a `bits` wide two's complement.
+    fn swap_low_bits(&self, bits: u64) -> Self;
+}
+
+impl BigReverse for BigUint {
+    fn swap_low_bits(&self, bits: u64) -> BigUint {
+        if bits == 0 {
+            return BigUint::default();
+        }
+
+        // Reversing the order of the limbs and the bits of each limb reverses the whole of the
+        // limbs, after which the reversed low bits are at the top and get shifted back down.
+        let limbs = bits.div_ceil(LIMB_BITS);
+        let reversed = reverse_limbs(self, limbs as usize);
+        reversed >> (limbs * LIMB_BITS - bits)
+    }
+}
+
+#[cfg(target_pointer_width = "64")]
+const LIMB_BITS: u64 = 64;
+#[cfg(not(target_pointer_width = "64"))]
+const LIMB_BITS: u64 = 32;
+
+// Pads `value` to `limbs` limbs and reverses all of their bits.
+#[cfg(target_pointer_width = "64")]
+fn reverse_limbs(value: &BigUint, limbs: usize) -> BigUint {
+    let mut digits = value.to_u64_digits();
+    digits.resize(limbs, 0);
+    // `BigUint` is only built from `u32` digits, so each reversed limb is split in two.
+    let reversed = digits
+        .iter()
+        .rev()
+        .map(|&limb| ParallelReverse::swap_bits(limb))
+        .flat_map(|limb| [limb as u32, (limb >> 32) as u32])
+        .collect();
+    BigUint::new(reversed)
+}
+
+#[cfg(not(target_pointer_width = "64"))]
+fn reverse_limbs(value: &BigUint, limbs: usize) -> BigUint {
+    let mut digits = value.to_u32_digits();
+    digits.resize(limbs, 0);
+    let reversed = digits
+        .iter()
+        .rev()
+        .map(|&limb| ParallelReverse::swap_bits(limb))
+        .collect();
+    BigUint::new(reversed)
+}
+
+impl BigReverse for BigInt {
+    fn swap_low_bits(&self, bits: u64) -> BigInt {
+        if bits == 0 {
+            return BigInt::default();
+        }
+
+        let modulus = BigInt::from(1u8) << bits;
+        let mut low = self % &modulus;
+        if low.sign() == Sign::Minus {
+            low += &modulus;
+        }
+        let reversed = BigInt::from(low.magnitude().swap_low_bits(bits));
+        if reversed.bit(bits - 1) {
+            reversed - modulus
+        } else {
+            reversed
+        }
+    }
+}
+
+#[test]
+fn matches_primitives() {
+    for x in ::xorshift().take(1000) {
+        let big = BigUint::from(x);
+        assert_eq!(big.swap_low_bits(64), BigUint::from(x.swap_bits()));
+        assert_eq!(big.swap_low_bits(20), BigUint::from(x.swap_low_bits(20)));
+        assert_eq!(
+            BigInt::from(x as i64).swap_low_bits(64),
+            BigInt::from((x as i64).swap_bits())
+        );
+        assert_eq!(
+            BigInt::from(x as i16).swap_low_bits(16),
+            BigInt::from((x as i16).swap_bits())
+        );
+    }
+}
+
+#[test]
+fn wide_values() {
+    let one = BigUint::from(1u8);
+    assert_eq!(one.swap_low_bits(4000), &one << 3999u32);
+    assert_eq!((&one << 3999u32).swap_low_bits(4000), one);
+    assert_eq!(one.swap_low_bits(0), BigUint::default());
+
+    // Bits above the width are dropped.
+    let value = (BigUint::from(0xABu8) << 300u32) | BigUint::from(0b1011u8);
+    assert_eq!(value.swap_low_bits(4), BigUint::from(0b1101u8));
+
+    // Reversing twice gives back the low bits.
+    let value = BigUint::from(0x0123456789ABCDEFu64).pow(5);
+    let width = value.bits() + 17;
+    assert_eq!(value.swap_low_bits(width).swap_low_bits(width), value);
+}
+
+#[test]
+fn twos_complement() {
+    assert_eq!(BigInt::from(-1).swap_low_bits(300), BigInt::from(-1));
+    assert_eq!(BigInt::from(-2).swap_low_bits(8), BigInt::from(0x7F));
+    assert_eq!(BigInt::from(-128).swap_low_bits(8), BigInt::from(1));
+    assert_eq!(
+        BigInt::from(1).swap_low_bits(200),
+        -(BigInt::from(1) << 199u32)
+    );
+}
@@ -0,0 +1,478 @@
+//! Readers and writers of n-bit fields in LSB-first or MSB-first bit streams.
+//!
+//! In an LSB-first stream the first bit is the least significant bit of the first byte, and the
+//! first bit of a field is its least significant bit. In an MSB-first stream both start from the
+//! most significant end instead. Internally every stream is handled LSB-first: MSB-first bytes
+//! are bit reversed on their way in and out, and MSB-first fields are reversed to their width.
+//!
+//! ```
+//! use bit_reverse::bitio::{BitReader, BitWriter};
+//! use bit_reverse::BitOrder;
+//!
+//! let mut buf = [0u8; 2];
+//! let mut writer = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+//! writer.write_bits(0b101, 3).unwrap();
+//! writer.write_bits(0x1FF, 9).unwrap();
+//! assert_eq!(buf, [0b1011_1111, 0b1111_0000]);
+//!
+//! let mut reader = BitReader::new(&buf, BitOrder::MsbFirst);
+//! assert_eq!(reader.read_bits(3), Some(0b101));
+//! assert_eq!(reader.read_bits(9), Some(0x1FF));
+//! ```
+
+use core::fmt;
+#[cfg(feature = "use_std")]
+use core::io;
+
+use BitOrder;
+use LookupReverse;
+use ParallelReverse;
+
+// Converts a stored byte into its LSB-first form and back, which is the same operation.
+#[inline]
+fn lsb_first_byte(byte: u8, order: BitOrder) -> u8 {
+    match order {
+        BitOrder::LsbFirst => byte,
+        BitOrder::MsbFirst => LookupReverse::swap_bits(byte),
+    }
+}
+
+// Converts a field between the LSB-first form it is streamed in and its value.
+#[inline]
+fn lsb_first_field(value: u64, bits: u32, order: BitOrder) -> u64 {
+    match order {
+        BitOrder::LsbFirst => value,
+        BitOrder::MsbFirst => ParallelReverse::swap_low_bits(value, bits),
+    }
+}
+
+#[inline]
+fn low_mask(bits: u32) -> u64 {
+    if bits == 64 {
+        !0
+    } else {
+        (1 << bits) - 1
+    }
+}
+
+/// The error returned when a `BitWriter` runs out of room in its buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferFull;
+
+impl fmt::Display for BufferFull {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bit writer buffer is full")
+    }
+}
+
+#[cfg(feature = "use_std")]
+impl ::std::error::Error for BufferFull {}
+
+/// Reads n-bit fields from a byte slice.
+#[derive(Clone, Debug)]
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    order: BitOrder,
+    // The stream position in bits. A backward reader reads the bits just below it.
+    pos: usize,
+    backward: bool,
+}
+
+impl<'a> BitReader<'a> {
+    /// Reads `data` from its first bit onward.
+    pub fn new(data: &'a [u8], order: BitOrder) -> BitReader<'a> {
+        BitReader {
+            data,
+            order,
+            pos: 0,
+            backward: false,
+        }
+    }
+
+    /// Reads `data` from its last bit back towards its first, as formats like Zstd's FSE streams
+    /// do. Every field is the `n` bits just before the current position, with the same bit
+    /// numbering as a forward reader would give it.
+    pub fn backward(data: &'a [u8], order: BitOrder) -> BitReader<'a> {
+        BitReader {
+            data,
+            order,
+            pos: 8 * data.len(),
+            backward: true,
+        }
+    }
+
+    /// The number of bits left to read.
+    pub fn bits_remaining(&self) -> usize {
+        if self.backward {
+            self.pos
+        } else {
+            8 * self.data.len() - self.pos
+        }
+    }
+
+    /// Reads an `n` bit field, or returns `None` without consuming anything if fewer than `n`
+    /// bits are left.
+    ///
+    /// # Panics
+    /// Panics if `n` is larger than 64.
+    pub fn read_bits(&mut self, n: u32) -> Option<u64> {
+        let value = self.peek_bits(n)?;
+        if self.backward {
+            self.pos -= n as usize;
+        } else {
+            self.pos += n as usize;
+        }
+        Some(value)
+    }
+
+    /// Reads an `n` bit field without consuming it.
+    ///
+    /// # Panics
+    /// Panics if `n` is larger than 64.
+    pub fn peek_bits(&self, n: u32) -> Option<u64> {
+        assert!(n <= 64, "cannot read more than 64 bits at once");
+        if self.bits_remaining() < n as usize {
+            return None;
+        }
+
+        let mut pos = if self.backward {
+            self.pos - n as usize
+        } else {
+            self.pos
+        };
+        let mut value = 0;
+        let mut got = 0;
+        while got < n {
+            let offset = (pos % 8) as u32;
+            let take = (8 - offset).min(n - got);
+            let byte = lsb_first_byte(self.data[pos / 8], self.order) >> offset;
+            value |= (byte as u64 & low_mask(take)) << got;
+            got += take;
+            pos += take as usize;
+        }
+        Some(lsb_first_field(value, n, self.order))
+    }
+
+    /// Reads a single bit.
+    pub fn read_bit(&mut self) -> Option<bool> {
+        self.read_bits(1).map(|bit| bit != 0)
+    }
+
+    /// Skips `n` bits, or returns `None` without moving if fewer than `n` bits are left.
+    pub fn skip_bits(&mut self, n: usize) -> Option<()> {
+        if self.bits_remaining() < n {
+            return None;
+        }
+        if self.backward {
+            self.pos -= n;
+        } else {
+            self.pos += n;
+        }
+        Some(())
+    }
+
+    /// Consumes zero bits up to and including the first set bit, which is how backward streams
+    /// mark where their data ends. Returns the number of bits consumed, or `None` if no bit is
+    /// set.
+    pub fn skip_padding(&mut self) -> Option<u32> {
+        let mut skipped = 0;
+        loop {
+            skipped += 1;
+            if self.read_bit()? {
+                return Some(skipped);
+            }
+        }
+    }
+}
+
+/// Writes n-bit fields into a byte slice.
+#[derive(Debug)]
+pub struct BitWriter<'a> {
+    data: &'a mut [u8],
+    order: BitOrder,
+    pos: usize,
+}
+
+impl<'a> BitWriter<'a> {
+    /// Writes into `data` from its first bit onward. Bits that are never written keep their
+    /// previous values.
+    pub fn new(data: &'a mut [u8], order: BitOrder) -> BitWriter<'a> {
+        BitWriter {
+            data,
+            order,
+            pos: 0,
+        }
+    }
+
+    /// The number of bits written so far.
+    pub fn bits_written(&self) -> usize {
+        self.pos
+    }
+
+    /// Writes the low `n` bits of `value`, or returns `BufferFull` without writing anything if
+    /// fewer than `n` bits of room are left.
+    ///
+    /// # Panics
+    /// Panics if `n` is larger than 64.
+    pub fn write_bits(&mut self, value: u64, n: u32) -> Result<(), BufferFull> {
+        assert!(n <= 64, "cannot write more than 64 bits at once");
+        if 8 * self.data.len() - self.pos < n as usize {
+            return Err(BufferFull);
+        }
+
+        let mut value = lsb_first_field(value & low_mask(n), n, self.order);
+        let mut left = n;
+        while left > 0 {
+            let offset = (self.pos % 8) as u32;
+            let take = (8 - offset).min(left);
+            let mask = (low_mask(take) as u8) << offset;
+            let byte = &mut self.data[self.pos / 8];
+            let current = lsb_first_byte(*byte, self.order);
+            let updated = (current & !mask) | (((value as u8) << offset) & mask);
+            *byte = lsb_first_byte(updated, self.order);
+
+            value >>= take;
+            left -= take;
+            self.pos += take as usize;
+        }
+        Ok(())
+    }
+
+    /// Writes a single bit.
+    pub fn write_bit(&mut self, bit: bool) -> Result<(), BufferFull> {
+        self.write_bits(bit as u64, 1)
+    }
+
+    /// Skips `n` bits, leaving them unchanged, or returns `BufferFull` without moving if fewer
+    /// than `n` bits of room are left.
+    pub fn skip_bits(&mut self, n: usize) -> Result<(), BufferFull> {
+        if 8 * self.data.len() - self.pos < n {
+            return Err(BufferFull);
+        }
+        self.pos += n;
+        Ok(())
+    }
+
+    /// Returns the number of bytes the written bits touch.
+    pub fn finish(self) -> usize {
+        self.pos.div_ceil(8)
+    }
+}
+
+/// Reads n-bit fields from an `io::Read`.
+#[cfg(feature = "use_std")]
+#[derive(Debug)]
+pub struct IoBitReader<R> {
+    inner: R,
+    order: BitOrder,
+    // The LSB-first bits of the current byte that haven't been read yet.
+    acc: u8,
+    count: u32,
+}
+
+#[cfg(feature = "use_std")]
+impl<R: io::Read> IoBitReader<R> {
+    /// Reads bits from `inner`, one byte at a time as they are needed.
+    pub fn new(inner: R, order: BitOrder) -> IoBitReader<R> {
+        IoBitReader {
+            inner,
+            order,
+            acc: 0,
+            count: 0,
+        }
+    }
+
+    /// Reads an `n` bit field.
+    ///
+    /// # Panics
+    /// Panics if `n` is larger than 64.
+    pub fn read_bits(&mut self, n: u32) -> io::Result<u64> {
+        assert!(n <= 64, "cannot read more than 64 bits at once");
+        let mut value = 0;
+        let mut got = 0;
+        while got < n {
+            if self.count == 0 {
+                let mut byte = [0];
+                self.inner.read_exact(&mut byte)?;
+                self.acc = lsb_first_byte(byte[0], self.order);
+                self.count = 8;
+            }
+            let take = self.count.min(n - got);
+            value |= (self.acc as u64 & low_mask(take)) << got;
+            self.acc = (self.acc as u16 >> take) as u8;
+            self.count -= take;
+            got += take;
+        }
+        Ok(lsb_first_field(value, n, self.order))
+    }
+
+    /// Reads a single bit.
+    pub fn read_bit(&mut self) -> io::Result<bool> {
+        self.read_bits(1).map(|bit| bit != 0)
+    }
+
+    /// Discards the unread bits of the current byte.
+    pub fn align_to_byte(&mut self) {
+        self.acc = 0;
+        self.count = 0;
+    }
+
+    /// Returns the underlying reader. Unread bits of the current byte are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// Writes n-bit fields to an `io::Write`.
+#[cfg(feature = "use_std")]
+#[derive(Debug)]
+pub struct IoBitWriter<W> {
+    inner: W,
+    order: BitOrder,
+    // The LSB-first bits of the current byte that haven't been written yet.
+    acc: u8,
+    count: u32,
+}
+
+#[cfg(feature = "use_std")]
+impl<W: io::Write> IoBitWriter<W> {
+    /// Writes bits to `inner`, one byte at a time as they are completed.
+    pub fn new(inner: W, order: BitOrder) -> IoBitWriter<W> {
+        IoBitWriter {
+            inner,
+            order,
+            acc: 0,
+            count: 0,
+        }
+    }
+
+    /// Writes the low `n` bits of `value`.
+    ///
+    /// # Panics
+    /// Panics if `n` is larger than 64.
+    pub fn write_bits(&mut self, value: u64, n: u32) -> io::Result<()> {
+        assert!(n <= 64, "cannot write more than 64 bits at once");
+        let mut value = lsb_first_field(value & low_mask(n), n, self.order);
+        let mut left = n;
+        while left > 0 {
+            let take = (8 - self.count).min(left);
+            self.acc |= ((value & low_mask(take)) as u8) << self.count;
+            self.count += take;
+            value >>= take;
+            left -= take;
+            if self.count == 8 {
+                self.inner
+                    .write_all(&[lsb_first_byte(self.acc, self.order)])?;
+                self.acc = 0;
+                self.count = 0;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a single bit.
+    pub fn write_bit(&mut self, bit: bool) -> io::Result<()> {
+        self.write_bits(bit as u64, 1)
+    }
+
+    /// Pads the current byte with zero bits and writes it, if any of its bits were written.
+    pub fn align_to_byte(&mut self) -> io::Result<()> {
+        if self.count > 0 {
+            let pad = 8 - self.count;
+            self.write_bits(0, pad)?;
+        }
+        Ok(())
+    }
+
+    /// Pads the last byte with zero bits, flushes it and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.align_to_byte()?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+#[test]
+fn lsb_first_fields() {
+    let data = [0b1011_0101, 0b0000_0011];
+    let mut reader = BitReader::new(&data, BitOrder::LsbFirst);
+    assert_eq!(reader.read_bits(3), Some(0b101));
+    assert_eq!(reader.read_bits(7), Some(0b1110110));
+    assert_eq!(reader.bits_remaining(), 6);
+    assert_eq!(reader.read_bits(7), None);
+    assert_eq!(reader.read_bits(6), Some(0));
+}
+
+#[test]
+fn msb_first_fields() {
+    let data = [0b1011_0101, 0b1100_0000];
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    assert_eq!(reader.read_bits(3), Some(0b101));
+    assert_eq!(reader.read_bits(7), Some(0b1010111));
+    assert_eq!(reader.read_bit(), Some(false));
+}
+
+#[test]
+fn wide_fields() {
+    let data = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0xFF];
+    let mut reader = BitReader::new(&data, BitOrder::MsbFirst);
+    assert_eq!(reader.read_bits(64), Some(0x0123456789ABCDEF));
+    let mut reader = BitReader::new(&data, BitOrder::LsbFirst);
+    assert_eq!(reader.read_bits(4), Some(0x1));
+    assert_eq!(reader.read_bits(64), Some(0xFEFCDAB896745230));
+}
+
+#[test]
+fn backward_reader() {
+    // A little-endian stream 0x8E5 read from its end: padding marker, then 3 bits, then 8 bits.
+    let data = [0xE5, 0x08];
+    let mut reader = BitReader::backward(&data, BitOrder::LsbFirst);
+    assert_eq!(reader.skip_padding(), Some(5));
+    assert_eq!(reader.read_bits(3), Some(0b000));
+    assert_eq!(reader.read_bits(8), Some(0xE5));
+    assert_eq!(reader.read_bits(1), None);
+}
+
+#[test]
+fn writer_round_trip() {
+    for &order in [BitOrder::LsbFirst, BitOrder::MsbFirst].iter() {
+        let mut buf = [0xFFu8; 16];
+        {
+            let mut writer = BitWriter::new(&mut buf, order);
+            for n in 1..16 {
+                writer.write_bits(0x5A5A ^ n as u64, n).unwrap();
+            }
+            assert_eq!(writer.write_bits(0, 64), Err(BufferFull));
+            assert_eq!(writer.finish(), 15);
+        }
+        let mut reader = BitReader::new(&buf, order);
+        for n in 1..16 {
+            let expected = (0x5A5A ^ n as u64) & low_mask(n);
+            assert_eq!(reader.read_bits(n), Some(expected));
+        }
+    }
+}
+
+#[cfg(feature = "use_std")]
+#[test]
+fn io_round_trip() {
+    for &order in [BitOrder::LsbFirst, BitOrder::MsbFirst].iter() {
+        let mut writer = IoBitWriter::new(Vec::new(), order);
+        for n in 0..=64 {
+            writer.write_bits(0x0123456789ABCDEF, n).unwrap();
+        }
+        let bytes = writer.finish().unwrap();
+
+        let mut slice_reader = BitReader::new(&bytes, order);
+        let mut io_reader = IoBitReader::new(&bytes[..], order);
+        for n in 0..=64 {
+            let expected = 0x0123456789ABCDEF & low_mask(n);
+            assert_eq!(slice_reader.read_bits(n), Some(expected));
+            assert_eq!(io_reader.read_bits(n).unwrap(), expected);
+        }
+        assert_eq!(
+            io_reader.read_bits(16).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+}
@@ -1,45 +1,81 @@
+//! The bitwise algorithm, along with `const fn` versions of it for every primitive integer.
+//!
+//! ```
+//! use bit_reverse::bitwise::reverse_u8;
+//!
+//! const MSB_FIRST: [u8; 2] = [reverse_u8(0x01), reverse_u8(0x03)];
+//! assert_eq!(MSB_FIRST, [0x80, 0xC0]);
+//! ```
+
 use super::core;
 
 /// Computes bit reversal by going bit by bit and setting the reverse position bit for the output.
+///
+/// # Timing
+/// Not constant time. The loop stops early once no set bits remain, so the running time depends
+/// on the position of the highest set bit.
 pub trait BitwiseReverse {
     /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
     fn swap_bits(self) -> Self;
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    fn swap_low_bits(self, bits: u32) -> Self;
 }
 
-macro_rules! doit_bitwise { ($($ty:ty),*) => ($(
-    impl BitwiseReverse for $ty {
-        // This algorithm uses the reverse variable as a like a stack to reverse the value.
-        // The lesser significant bits are pushed onto the reverse variable and then the variable
-        // is shifted down to make room for more significant bits. This algorithm has a shortcut,
-        // that if there aren't anymore 1s to push onto the reverse variable the algorithm ends
-        // early and shift the reverse to the correct position.
-        #[inline]
-        fn swap_bits(self) -> $ty {
-            let mut v = self;
+macro_rules! doit_bitwise { ($($name:ident: $ty:ty),*) => ($(
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the type.
+    // This algorithm uses the reverse variable as a like a stack to reverse the value.
+    // The lesser significant bits are pushed onto the reverse variable and then the variable
+    // is shifted down to make room for more significant bits. This algorithm has a shortcut,
+    // that if there aren't anymore 1s to push onto the reverse variable the algorithm ends
+    // early and shift the reverse to the correct position.
+    #[inline]
+    pub const fn $name(value: $ty) -> $ty {
+        let mut v = value;
 
-            // By initializing the reversal to value, we have already loaded the largest
-            // significant bit into the correct location.
-            let mut r = self;
+        // By initializing the reversal to value, we have already loaded the largest
+        // significant bit into the correct location.
+        let mut r = value;
 
-            // Compute how many bits are left to shift at the end of the algorithm.
-            let mut s = 8 * core::mem::size_of::<$ty>() - 1;
+        // Compute how many bits are left to shift at the end of the algorithm.
+        let mut s = 8 * core::mem::size_of::<$ty>() - 1;
 
-            v >>= 1;
-            while v != 0 {  // Quit early if there are no more 1s to shift in
-                r <<= 1;    // Make room for the next significant bit
-                r |= v & 1; // Add the bit to the reverse variable
-                v >>= 1;    // Go to the next significant bit
-                s -= 1;     // Decrement the leftover bit count
-            }
+        v >>= 1;
+        while v != 0 {  // Quit early if there are no more 1s to shift in
+            r <<= 1;    // Make room for the next significant bit
+            r |= v & 1; // Add the bit to the reverse variable
+            v >>= 1;    // Go to the next significant bit
+            s -= 1;     // Decrement the leftover bit count
+        }
 
-            // Shift the reversal to the correct position and return the reversal
-            r << s
+        // Shift the reversal to the correct position and return the reversal
+        r << s
+    }
+
+    impl BitwiseReverse for $ty {
+        #[inline]
+        fn swap_bits(self) -> $ty {
+            $name(self)
         }
+
+        swap_low_bits!(BitwiseReverse, $ty);
     })*)
 }
 
-doit_bitwise!(u8, u16, u32, u64, usize);
+doit_bitwise!(
+    reverse_u8: u8,
+    reverse_u16: u16,
+    reverse_u32: u32,
+    reverse_u64: u64,
+    reverse_usize: usize
+);
 #[cfg(feature = "u128")]
-doit_bitwise!(u128);
+doit_bitwise!(reverse_u128: u128);
+const_signed!();
 doit_signed!(BitwiseReverse);
 test_suite!();
+const_test_suite!();
@@ -0,0 +1,123 @@
+/// Computes bit reversal with the same swaps as `ParallelReverse`, with optimization barriers
+/// between the steps so the compiler can't specialize the code on the value being reversed.
+///
+/// # Timing
+/// Branch-free and table-free, so the running time of `swap_bits` doesn't depend on the value.
+/// The running time of `swap_low_bits` depends on `bits`, but not on the value. The barriers make
+/// this slower than `ParallelReverse`.
+pub trait ConstTimeReverse {
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
+    fn swap_bits(self) -> Self;
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    fn swap_low_bits(self, bits: u32) -> Self;
+}
+
+// Hides a value from the optimizer by forcing it through memory.
+#[inline(always)]
+fn barrier<T: Copy>(v: T) -> T {
+    unsafe { ::core::ptr::read_volatile(&v) }
+}
+
+macro_rules! doit_const_time { ($($ty:ty),*) => ($(
+    impl ConstTimeReverse for $ty {
+        #[inline]
+        fn swap_bits(self) -> $ty {
+            let mut v = barrier(self);
+            // Swap odd and even bits
+            v = barrier(((v >> 1) & (0x55555555555555555555555555555555 as $ty)) | ((v & (0x55555555555555555555555555555555 as $ty)) << 1));
+            // Swap consecutive pairs
+            v = barrier(((v >> 2) & (0x33333333333333333333333333333333 as $ty)) | ((v & (0x33333333333333333333333333333333 as $ty)) << 2));
+            // Swap nibbles
+            v = barrier(((v >> 4) & (0x0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F as $ty)) | ((v & (0x0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F as $ty)) << 4));
+
+            v.swap_bytes()
+        }
+
+        swap_low_bits!(ConstTimeReverse, $ty);
+    })*)
+}
+
+doit_const_time!(u8, u16, u32, u64, usize);
+#[cfg(feature = "u128")]
+doit_const_time!(u128);
+doit_signed!(ConstTimeReverse);
+test_suite!();
+
+// Computes Welch's t statistic between the running times of `f` on a fixed input and on random
+// inputs, as dudect does. A magnitude above 4.5 is strong evidence that the timing depends on the
+// input. The measurements are only meaningful in release builds, so the tests using this are
+// ignored by default and run with `cargo test --release -- --ignored --test-threads=1 timing`.
+#[cfg(all(test, feature = "use_std"))]
+fn timing_t_statistic<T: Copy>(f: fn(T) -> T, fixed: T, from_bits: fn(u64) -> T) -> f64 {
+    use std::hint::black_box;
+    use std::time::Instant;
+
+    const MEASUREMENTS: usize = 50_000;
+    const BATCH: usize = 256;
+
+    let mut random = ::xorshift();
+    let mut next = move || random.next().unwrap();
+
+    // Interleave the classes randomly so that drift in the machine's speed affects both equally.
+    let mut times = [Vec::new(), Vec::new()];
+    for _ in 0..MEASUREMENTS {
+        let class = (next() & 1) as usize;
+        // Both classes do the same work preparing their inputs.
+        let mut inputs = [fixed; BATCH];
+        for input in inputs.iter_mut() {
+            let random = from_bits(next());
+            *input = if class == 1 { random } else { fixed };
+        }
+        let start = Instant::now();
+        for &input in inputs.iter() {
+            black_box(f(black_box(input)));
+        }
+        times[class].push(start.elapsed().as_nanos() as f64);
+    }
+
+    // Drop the slowest tenth of the measurements, which are mostly interrupts and preemption.
+    let mut all: Vec<f64> = times.iter().flat_map(|t| t.iter().cloned()).collect();
+    all.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let cutoff = all[all.len() * 9 / 10];
+
+    let mut stats = [(0.0, 0.0, 0.0); 2];
+    for (class, times) in times.iter().enumerate() {
+        let kept: Vec<f64> = times.iter().cloned().filter(|&t| t <= cutoff).collect();
+        let n = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / n;
+        let variance = kept.iter().map(|t| (t - mean) * (t - mean)).sum::<f64>() / (n - 1.0);
+        stats[class] = (n, mean, variance);
+    }
+    let ((n0, mean0, var0), (n1, mean1, var1)) = (stats[0], stats[1]);
+    (mean0 - mean1) / (var0 / n0 + var1 / n1).sqrt()
+}
+
+#[cfg(feature = "use_std")]
+#[test]
+#[ignore]
+fn timing_const_time_u64() {
+    let t = timing_t_statistic(ConstTimeReverse::swap_bits, 0u64, |x| x);
+    assert!(t.abs() < 4.5, "t = {}", t);
+}
+
+#[cfg(feature = "use_std")]
+#[test]
+#[ignore]
+fn timing_const_time_u8() {
+    let t = timing_t_statistic(ConstTimeReverse::swap_bits, 0u8, |x| x as u8);
+    assert!(t.abs() < 4.5, "t = {}", t);
+}
+
+// Checks that the test can see a leak at all, using the early exit of `BitwiseReverse`.
+#[cfg(feature = "use_std")]
+#[test]
+#[ignore]
+fn timing_detects_bitwise_leak() {
+    let t = timing_t_statistic(::BitwiseReverse::swap_bits, 0u64, |x| x);
+    assert!(t.abs() > 4.5, "t = {}", t);
+}
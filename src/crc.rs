@@ -0,0 +1,459 @@
+//! Configurable CRC engines following the Rocksoft parameter model used by the
+//! [CRC catalogue](https://reveng.sourceforge.io/crc-catalogue/).
+//!
+//! ```
+//! use bit_reverse::crc::{Crc, CRC_32};
+//!
+//! assert_eq!(Crc::new(CRC_32).checksum(b"123456789"), 0xCBF43926);
+//! ```
+
+use ParallelReverse;
+
+/// The parameters of a CRC algorithm as listed in the CRC catalogue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Algorithm {
+    /// The width of the CRC register in bits, between 1 and 64.
+    pub width: u8,
+    /// The generator polynomial without its leading term, written MSB-first.
+    pub poly: u64,
+    /// The register value before any data is processed, written MSB-first.
+    pub init: u64,
+    /// Whether every input byte is reflected, i.e. processed LSB-first.
+    pub refin: bool,
+    /// Whether the final register is reflected before `xorout` is applied.
+    pub refout: bool,
+    /// The value XORed into the reflected or unreflected register to produce the checksum.
+    pub xorout: u64,
+    /// The checksum of the ASCII string "123456789".
+    pub check: u64,
+}
+
+/// CRC-3/GSM
+pub const CRC_3_GSM: Algorithm = Algorithm {
+    width: 3,
+    poly: 0x3,
+    init: 0x0,
+    refin: false,
+    refout: false,
+    xorout: 0x7,
+    check: 0x4,
+};
+
+/// CRC-5/USB
+pub const CRC_5_USB: Algorithm = Algorithm {
+    width: 5,
+    poly: 0x05,
+    init: 0x1F,
+    refin: true,
+    refout: true,
+    xorout: 0x1F,
+    check: 0x19,
+};
+
+/// CRC-8/MAXIM-DOW, also known as CRC-8/MAXIM.
+pub const CRC_8_MAXIM_DOW: Algorithm = Algorithm {
+    width: 8,
+    poly: 0x31,
+    init: 0x00,
+    refin: true,
+    refout: true,
+    xorout: 0x00,
+    check: 0xA1,
+};
+
+/// CRC-12/UMTS, which reflects its output but not its input.
+pub const CRC_12_UMTS: Algorithm = Algorithm {
+    width: 12,
+    poly: 0x80F,
+    init: 0x000,
+    refin: false,
+    refout: true,
+    xorout: 0x000,
+    check: 0xDAF,
+};
+
+/// CRC-16/ARC
+pub const CRC_16_ARC: Algorithm = Algorithm {
+    width: 16,
+    poly: 0x8005,
+    init: 0x0000,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+    check: 0xBB3D,
+};
+
+/// CRC-16/IBM-3740, also known as CRC-16/CCITT-FALSE.
+pub const CRC_16_IBM_3740: Algorithm = Algorithm {
+    width: 16,
+    poly: 0x1021,
+    init: 0xFFFF,
+    refin: false,
+    refout: false,
+    xorout: 0x0000,
+    check: 0x29B1,
+};
+
+/// CRC-16/KERMIT, also known as CRC-16/CCITT.
+pub const CRC_16_KERMIT: Algorithm = Algorithm {
+    width: 16,
+    poly: 0x1021,
+    init: 0x0000,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+    check: 0x2189,
+};
+
+/// CRC-16/XMODEM
+pub const CRC_16_XMODEM: Algorithm = Algorithm {
+    width: 16,
+    poly: 0x1021,
+    init: 0x0000,
+    refin: false,
+    refout: false,
+    xorout: 0x0000,
+    check: 0x31C3,
+};
+
+/// CRC-32/BZIP2
+pub const CRC_32_BZIP2: Algorithm = Algorithm {
+    width: 32,
+    poly: 0x04C11DB7,
+    init: 0xFFFFFFFF,
+    refin: false,
+    refout: false,
+    xorout: 0xFFFFFFFF,
+    check: 0xFC891918,
+};
+
+/// CRC-32/ISCSI, also known as CRC-32C.
+pub const CRC_32_ISCSI: Algorithm = Algorithm {
+    width: 32,
+    poly: 0x1EDC6F41,
+    init: 0xFFFFFFFF,
+    refin: true,
+    refout: true,
+    xorout: 0xFFFFFFFF,
+    check: 0xE3069283,
+};
+
+/// CRC-32/ISO-HDLC, the CRC-32 used by Ethernet, zlib and PNG.
+pub const CRC_32_ISO_HDLC: Algorithm = Algorithm {
+    width: 32,
+    poly: 0x04C11DB7,
+    init: 0xFFFFFFFF,
+    refin: true,
+    refout: true,
+    xorout: 0xFFFFFFFF,
+    check: 0xCBF43926,
+};
+
+/// CRC-64/ECMA-182
+pub const CRC_64_ECMA_182: Algorithm = Algorithm {
+    width: 64,
+    poly: 0x42F0E1EBA9EA3693,
+    init: 0x0000000000000000,
+    refin: false,
+    refout: false,
+    xorout: 0x0000000000000000,
+    check: 0x6C40DF5F0B497347,
+};
+
+/// CRC-64/XZ
+pub const CRC_64_XZ: Algorithm = Algorithm {
+    width: 64,
+    poly: 0x42F0E1EBA9EA3693,
+    init: 0xFFFFFFFFFFFFFFFF,
+    refin: true,
+    refout: true,
+    xorout: 0xFFFFFFFFFFFFFFFF,
+    check: 0x995DC9BBDF1939FA,
+};
+
+/// Alias of `CRC_8_MAXIM_DOW`.
+pub const CRC_8_MAXIM: Algorithm = CRC_8_MAXIM_DOW;
+/// Alias of `CRC_16_KERMIT`.
+pub const CRC_16_CCITT: Algorithm = CRC_16_KERMIT;
+/// Alias of `CRC_32_ISO_HDLC`.
+pub const CRC_32: Algorithm = CRC_32_ISO_HDLC;
+
+impl Algorithm {
+    // Reflected algorithms keep the reflected register in the low `width` bits, so bytes shift in
+    // from the top and leave from the bottom. Unreflected algorithms keep the register in the
+    // high `width` bits of the u64, so bytes line up with its most significant byte.
+    fn shift(&self) -> u32 {
+        64 - self.width as u32
+    }
+
+    fn reflect(&self, value: u64) -> u64 {
+        value.swap_low_bits(self.width as u32)
+    }
+
+    fn table_entry(&self, byte: u8) -> u64 {
+        if self.refin {
+            let poly = self.reflect(self.poly);
+            let mut r = byte as u64;
+            for _ in 0..8 {
+                r = if r & 1 != 0 { (r >> 1) ^ poly } else { r >> 1 };
+            }
+            r
+        } else {
+            let poly = self.poly << self.shift();
+            let mut r = (byte as u64) << 56;
+            for _ in 0..8 {
+                r = if r >> 63 != 0 {
+                    (r << 1) ^ poly
+                } else {
+                    r << 1
+                };
+            }
+            r
+        }
+    }
+
+    fn table(&self) -> [u64; 256] {
+        assert!(
+            self.width >= 1 && self.width <= 64,
+            "CRC width must be between 1 and 64 bits"
+        );
+
+        let mut table = [0; 256];
+        for (i, entry) in table.iter_mut().enumerate() {
+            *entry = self.table_entry(i as u8);
+        }
+        table
+    }
+
+    fn initial_register(&self) -> u64 {
+        if self.refin {
+            self.reflect(self.init)
+        } else {
+            self.init << self.shift()
+        }
+    }
+
+    fn final_value(&self, register: u64) -> u64 {
+        // Recover the register in its MSB-first form before applying the output reflection.
+        let crc = if self.refin {
+            self.reflect(register)
+        } else {
+            register >> self.shift()
+        };
+        let crc = if self.refout { self.reflect(crc) } else { crc };
+        crc ^ self.xorout
+    }
+
+    #[inline]
+    fn update_byte(&self, table: &[u64; 256], register: u64, byte: u8) -> u64 {
+        if self.refin {
+            table[((register ^ byte as u64) & 0xFF) as usize] ^ (register >> 8)
+        } else {
+            table[((register >> 56) ^ byte as u64) as usize] ^ (register << 8)
+        }
+    }
+}
+
+/// Computes CRCs one byte at a time with a single 256 entry table.
+#[derive(Clone)]
+pub struct Crc {
+    algorithm: Algorithm,
+    table: [u64; 256],
+}
+
+impl Crc {
+    /// Builds the lookup table for `algorithm`.
+    ///
+    /// # Panics
+    /// Panics if the width of `algorithm` isn't between 1 and 64 bits.
+    pub fn new(algorithm: Algorithm) -> Crc {
+        Crc {
+            table: algorithm.table(),
+            algorithm,
+        }
+    }
+
+    /// The parameters this engine computes.
+    pub fn algorithm(&self) -> &Algorithm {
+        &self.algorithm
+    }
+
+    /// Computes the CRC of `bytes`.
+    pub fn checksum(&self, bytes: &[u8]) -> u64 {
+        self.finalize(self.update(self.init(), bytes))
+    }
+
+    /// The register state before any data has been processed.
+    pub fn init(&self) -> u64 {
+        self.algorithm.initial_register()
+    }
+
+    /// Feeds `bytes` into the register state returned by `init` or a previous `update`.
+    pub fn update(&self, register: u64, bytes: &[u8]) -> u64 {
+        bytes.iter().fold(register, |r, &b| {
+            self.algorithm.update_byte(&self.table, r, b)
+        })
+    }
+
+    /// Turns a register state into the CRC of all the data fed to it.
+    pub fn finalize(&self, register: u64) -> u64 {
+        self.algorithm.final_value(register)
+    }
+}
+
+/// Computes CRCs eight bytes at a time with eight 256 entry tables.
+#[derive(Clone)]
+pub struct SliceBy8Crc {
+    algorithm: Algorithm,
+    tables: [[u64; 256]; 8],
+}
+
+impl SliceBy8Crc {
+    /// Builds the lookup tables for `algorithm`.
+    ///
+    /// # Panics
+    /// Panics if the width of `algorithm` isn't between 1 and 64 bits.
+    pub fn new(algorithm: Algorithm) -> SliceBy8Crc {
+        let mut tables = [algorithm.table(); 8];
+        for k in 1..8 {
+            for i in 0..256 {
+                let prev = tables[k - 1][i];
+                tables[k][i] = if algorithm.refin {
+                    tables[0][(prev & 0xFF) as usize] ^ (prev >> 8)
+                } else {
+                    tables[0][(prev >> 56) as usize] ^ (prev << 8)
+                };
+            }
+        }
+        SliceBy8Crc { algorithm, tables }
+    }
+
+    /// The parameters this engine computes.
+    pub fn algorithm(&self) -> &Algorithm {
+        &self.algorithm
+    }
+
+    /// Computes the CRC of `bytes`.
+    pub fn checksum(&self, bytes: &[u8]) -> u64 {
+        self.finalize(self.update(self.init(), bytes))
+    }
+
+    /// The register state before any data has been processed.
+    pub fn init(&self) -> u64 {
+        self.algorithm.initial_register()
+    }
+
+    /// Feeds `bytes` into the register state returned by `init` or a previous `update`.
+    pub fn update(&self, register: u64, bytes: &[u8]) -> u64 {
+        let t = &self.tables;
+        let mut r = register;
+
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+
+            r = if self.algorithm.refin {
+                let v = r ^ u64::from_le_bytes(word);
+                t[7][(v & 0xFF) as usize]
+                    ^ t[6][((v >> 8) & 0xFF) as usize]
+                    ^ t[5][((v >> 16) & 0xFF) as usize]
+                    ^ t[4][((v >> 24) & 0xFF) as usize]
+                    ^ t[3][((v >> 32) & 0xFF) as usize]
+                    ^ t[2][((v >> 40) & 0xFF) as usize]
+                    ^ t[1][((v >> 48) & 0xFF) as usize]
+                    ^ t[0][(v >> 56) as usize]
+            } else {
+                let v = r ^ u64::from_be_bytes(word);
+                t[7][(v >> 56) as usize]
+                    ^ t[6][((v >> 48) & 0xFF) as usize]
+                    ^ t[5][((v >> 40) & 0xFF) as usize]
+                    ^ t[4][((v >> 32) & 0xFF) as usize]
+                    ^ t[3][((v >> 24) & 0xFF) as usize]
+                    ^ t[2][((v >> 16) & 0xFF) as usize]
+                    ^ t[1][((v >> 8) & 0xFF) as usize]
+                    ^ t[0][(v & 0xFF) as usize]
+            };
+        }
+
+        chunks
+            .remainder()
+            .iter()
+            .fold(r, |r, &b| self.algorithm.update_byte(&t[0], r, b))
+    }
+
+    /// Turns a register state into the CRC of all the data fed to it.
+    pub fn finalize(&self, register: u64) -> u64 {
+        self.algorithm.final_value(register)
+    }
+}
+
+#[cfg(test)]
+const PRESETS: [Algorithm; 13] = [
+    CRC_3_GSM,
+    CRC_5_USB,
+    CRC_8_MAXIM_DOW,
+    CRC_12_UMTS,
+    CRC_16_ARC,
+    CRC_16_IBM_3740,
+    CRC_16_KERMIT,
+    CRC_16_XMODEM,
+    CRC_32_BZIP2,
+    CRC_32_ISCSI,
+    CRC_32_ISO_HDLC,
+    CRC_64_ECMA_182,
+    CRC_64_XZ,
+];
+
+#[test]
+fn table_check_values() {
+    for algorithm in PRESETS.iter() {
+        assert_eq!(
+            Crc::new(*algorithm).checksum(b"123456789"),
+            algorithm.check,
+            "{:?}",
+            algorithm
+        );
+    }
+}
+
+#[test]
+fn slice_by_8_check_values() {
+    for algorithm in PRESETS.iter() {
+        assert_eq!(
+            SliceBy8Crc::new(*algorithm).checksum(b"123456789"),
+            algorithm.check,
+            "{:?}",
+            algorithm
+        );
+    }
+}
+
+#[test]
+fn slice_by_8_matches_table() {
+    let mut data = [0u8; 100];
+    for (i, byte) in data.iter_mut().enumerate() {
+        *byte = (i * 37 + 11) as u8;
+    }
+    for algorithm in PRESETS.iter() {
+        let table = Crc::new(*algorithm);
+        let slice = SliceBy8Crc::new(*algorithm);
+        for len in 0..data.len() {
+            assert_eq!(table.checksum(&data[..len]), slice.checksum(&data[..len]));
+        }
+    }
+}
+
+#[test]
+fn streaming_matches_one_shot() {
+    let crc = Crc::new(CRC_32);
+    let register = crc.update(crc.init(), b"1234");
+    let register = crc.update(register, b"56789");
+    assert_eq!(crc.finalize(register), CRC_32.check);
+}
+
+#[test]
+#[should_panic]
+fn zero_width_is_rejected() {
+    Crc::new(Algorithm { width: 0, ..CRC_32 });
+}
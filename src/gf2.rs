@@ -0,0 +1,278 @@
+//! Helpers for polynomials over GF(2) packed into integers, where the coefficient of x^k is
+//! bit k.
+//!
+//! ```
+//! use bit_reverse::gf2;
+//!
+//! // x^4 + x + 1 and its reciprocal x^4 + x^3 + 1.
+//! assert_eq!(gf2::reciprocal(0b10011, 4), 0b11001);
+//! ```
+
+use ParallelReverse;
+
+/// Returns the degree of `poly`, or `None` for the zero polynomial.
+pub fn degree(poly: u64) -> Option<u32> {
+    if poly == 0 {
+        None
+    } else {
+        Some(63 - poly.leading_zeros())
+    }
+}
+
+/// Reverses the coefficients of `poly` within `degree`+1 bits, i.e. computes x^degree * p(1/x).
+///
+/// # Panics
+/// Panics if `degree` is larger than 63 or `poly` has terms above x^degree.
+pub fn reciprocal(poly: u64, degree: u32) -> u64 {
+    assert!(degree < 64, "polynomial degree must be less than 64");
+    assert!(
+        poly >> degree >> 1 == 0,
+        "polynomial has terms above its degree"
+    );
+    poly.swap_low_bits(degree + 1)
+}
+
+/// Converts the characteristic polynomial of a Fibonacci LFSR into the polynomial of the Galois
+/// LFSR that generates the same sequence.
+///
+/// Both polynomials include their x^n and x^0 terms. The Fibonacci register shifts right and
+/// XORs the bits selected by the terms below x^n into its top bit. The Galois register shifts
+/// right and, when its output bit is set, XORs in the polynomial without its x^0 term shifted
+/// down by one.
+///
+/// # Panics
+/// Panics if `poly` is zero.
+pub fn fibonacci_to_galois(poly: u64) -> u64 {
+    reciprocal(
+        poly,
+        degree(poly).expect("LFSR polynomial must not be zero"),
+    )
+}
+
+/// Converts the polynomial of a Galois LFSR into the characteristic polynomial of the Fibonacci
+/// LFSR that generates the same sequence. This is the inverse of `fibonacci_to_galois`.
+///
+/// # Panics
+/// Panics if `poly` is zero.
+pub fn galois_to_fibonacci(poly: u64) -> u64 {
+    fibonacci_to_galois(poly)
+}
+
+/// Computes the carry-less product of `a` and `b`, returned as its (low, high) halves.
+///
+/// The `pclmulqdq` instruction is used when the CPU supports it, which is detected at runtime
+/// when the `use_std` feature is enabled.
+#[inline]
+pub fn clmul(a: u64, b: u64) -> (u64, u64) {
+    #[cfg(all(feature = "use_std", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("pclmulqdq") {
+            return unsafe { clmul_pclmulqdq(a, b) };
+        }
+    }
+    clmul_software(a, b)
+}
+
+/// Computes the carry-less product of `a` and `b` with a branch-free shift and XOR loop.
+pub fn clmul_software(a: u64, b: u64) -> (u64, u64) {
+    let mut lo = 0;
+    let mut hi = 0;
+    for i in 0..64 {
+        // All ones if bit i of b is set, otherwise all zeros.
+        let mask = 0u64.wrapping_sub((b >> i) & 1);
+        lo ^= (a << i) & mask;
+        // Split the shift so that i = 0 shifts by 64 without overflowing.
+        hi ^= ((a >> 1) >> (63 - i)) & mask;
+    }
+    (lo, hi)
+}
+
+#[cfg(all(feature = "use_std", target_arch = "x86_64"))]
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn clmul_pclmulqdq(a: u64, b: u64) -> (u64, u64) {
+    use core::arch::x86_64::*;
+
+    let r = _mm_clmulepi64_si128(
+        _mm_cvtsi64_si128(a as i64),
+        _mm_cvtsi64_si128(b as i64),
+        0x00,
+    );
+    (
+        _mm_cvtsi128_si64(r) as u64,
+        _mm_cvtsi128_si64(_mm_unpackhi_epi64(r, r)) as u64,
+    )
+}
+
+/// Computes the carry-less product of two bit-reflected operands, where bit 63 holds the
+/// coefficient of x^0. The product is returned bit-reflected across 128 bits as its (low, high)
+/// halves, so bit 127 of the result holds the coefficient of x^0.
+#[inline]
+pub fn clmul_reflected(a: u64, b: u64) -> (u64, u64) {
+    // Reversing both operands and the product is the same as multiplying unreversed and
+    // shifting by one, since the reflected product only spans 127 bits.
+    let (lo, hi) = clmul(a, b);
+    (lo << 1, (hi << 1) | (lo >> 63))
+}
+
+/// Multiplies two elements of GHASH's GF(2^128), reduced by x^128 + x^7 + x^2 + x + 1.
+///
+/// Blocks use the bit-reflected convention of GCM: the most significant bit of the first byte
+/// holds the coefficient of x^0.
+pub fn ghash_mul(x: &[u8; 16], y: &[u8; 16]) -> [u8; 16] {
+    let (x0, x1) = unreflect_block(x);
+    let (y0, y1) = unreflect_block(y);
+
+    // Schoolbook multiplication of the 128 bit operands into a 256 bit product.
+    let (p00, q00) = clmul(x0, y0);
+    let (p11, q11) = clmul(x1, y1);
+    let (p01, q01) = clmul(x0, y1);
+    let (p10, q10) = clmul(x1, y0);
+    let d0 = p00;
+    let d1 = q00 ^ p01 ^ p10;
+    let d2 = p11 ^ q01 ^ q10;
+    let d3 = q11;
+
+    // x^128 = x^7 + x^2 + x + 1, so the high half folds back as h * (x^7 + x^2 + x + 1). The
+    // bits that multiplication pushes past x^128 are folded into h first.
+    let carry = (d3 >> 63) ^ (d3 >> 62) ^ (d3 >> 57);
+    let h0 = d2 ^ carry;
+    let h1 = d3;
+    let r0 = d0 ^ h0 ^ (h0 << 1) ^ (h0 << 2) ^ (h0 << 7);
+    let r1 = d1 ^ h1 ^ (h1 << 1 | h0 >> 63) ^ (h1 << 2 | h0 >> 62) ^ (h1 << 7 | h0 >> 57);
+
+    reflect_block(r0, r1)
+}
+
+// Converts a GCM block into (low, high) halves of its polynomial with x^k at bit k.
+fn unreflect_block(block: &[u8; 16]) -> (u64, u64) {
+    let mut first = [0; 8];
+    let mut second = [0; 8];
+    first.copy_from_slice(&block[..8]);
+    second.copy_from_slice(&block[8..]);
+    (
+        u64::from_be_bytes(first).swap_bits(),
+        u64::from_be_bytes(second).swap_bits(),
+    )
+}
+
+fn reflect_block(lo: u64, hi: u64) -> [u8; 16] {
+    let mut block = [0; 16];
+    block[..8].copy_from_slice(&lo.swap_bits().to_be_bytes());
+    block[8..].copy_from_slice(&hi.swap_bits().to_be_bytes());
+    block
+}
+
+#[cfg(test)]
+fn parity(v: u64) -> u64 {
+    (v.count_ones() & 1) as u64
+}
+
+#[test]
+fn reciprocal_polynomials() {
+    assert_eq!(reciprocal(0b10011, 4), 0b11001);
+    assert_eq!(reciprocal(0b1011, 3), 0b1101);
+    assert_eq!(reciprocal(0b10, 3), 0b100);
+    assert_eq!(reciprocal(1 << 63 | 1, 63), 1 << 63 | 1);
+}
+
+#[test]
+#[should_panic]
+fn reciprocal_rejects_terms_above_degree() {
+    reciprocal(0b10011, 3);
+}
+
+#[test]
+fn galois_lfsr_follows_fibonacci_recurrence() {
+    // x^16 + x^5 + x^3 + x^2 + 1, the Fibonacci form of the classic 16 bit maximal LFSR.
+    let fibonacci = 1 << 16 | 1 << 5 | 1 << 3 | 1 << 2 | 1;
+    let galois = fibonacci_to_galois(fibonacci);
+    assert_eq!(galois, 0x16801);
+    assert_eq!(galois_to_fibonacci(galois), fibonacci);
+
+    // Run the Galois register and check its output against the Fibonacci recurrence.
+    let taps = fibonacci & !(1 << 16);
+    let mut state = 0xACE1u64;
+    let mut bits = 0u64;
+    for len in 0..64 {
+        let out = state & 1;
+        state >>= 1;
+        if out != 0 {
+            state ^= galois >> 1;
+        }
+        bits |= out << len;
+    }
+    for t in 0..(64 - 16) {
+        assert_eq!((bits >> (t + 16)) & 1, parity((bits >> t) & taps));
+    }
+}
+
+#[test]
+fn software_clmul() {
+    assert_eq!(clmul_software(0b11, 0b11), (0b101, 0));
+    assert_eq!(clmul_software(1 << 63, 1 << 63), (0, 1 << 62));
+    assert_eq!(clmul_software(!0, 1), (!0, 0));
+    assert_eq!(clmul_software(!0, 2), (!0 << 1, 1));
+}
+
+#[test]
+fn clmul_matches_software() {
+    let mut a = 0x0123456789ABCDEFu64;
+    let mut b = 0xFEDCBA9876543210u64;
+    for _ in 0..100 {
+        assert_eq!(clmul(a, b), clmul_software(a, b));
+        a = a.rotate_left(7) ^ b;
+        b = b.wrapping_mul(0x9E3779B97F4A7C15).wrapping_add(1);
+    }
+}
+
+#[test]
+fn reflected_clmul() {
+    let a = 0x0123456789ABCDEFu64;
+    let b = 0xFEDCBA9876543210u64;
+    let (lo, hi) = clmul(a.swap_bits(), b.swap_bits());
+    assert_eq!(clmul_reflected(a, b), (hi.swap_bits(), lo.swap_bits()));
+}
+
+#[test]
+fn ghash_test_vector() {
+    // GCM test case 2: H = E(K, 0^128) with the zero key.
+    let h = [
+        0x66, 0xE9, 0x4B, 0xD4, 0xEF, 0x8A, 0x2C, 0x3B, 0x88, 0x4C, 0xFA, 0x59, 0xCA, 0x34, 0x2B,
+        0x2E,
+    ];
+    let c = [
+        0x03, 0x88, 0xDA, 0xCE, 0x60, 0xB6, 0xA3, 0x92, 0xF3, 0x28, 0xC2, 0xB9, 0x71, 0xB2, 0xFE,
+        0x78,
+    ];
+    let mut x = ghash_mul(&c, &h);
+    assert_eq!(
+        x,
+        [
+            0x5E, 0x2E, 0xC7, 0x46, 0x91, 0x70, 0x62, 0x88, 0x2C, 0x85, 0xB0, 0x68, 0x53, 0x53,
+            0xDE, 0xB7
+        ]
+    );
+
+    // Fold in the length block for an empty AAD and a 128 bit ciphertext.
+    x[15] ^= 0x80;
+    assert_eq!(
+        ghash_mul(&x, &h),
+        [
+            0xF3, 0x8C, 0xBB, 0x1A, 0xD6, 0x92, 0x23, 0xDC, 0xC3, 0x45, 0x7A, 0xE5, 0xB6, 0xB0,
+            0xF8, 0x85
+        ]
+    );
+}
+
+#[test]
+fn ghash_identity() {
+    // The block with only its first bit set is the polynomial 1.
+    let mut one = [0; 16];
+    one[0] = 0x80;
+    let x = [
+        0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0xFE, 0xDC, 0xBA, 0x98, 0x76, 0x54, 0x32,
+        0x10,
+    ];
+    assert_eq!(ghash_mul(&x, &one), x);
+    assert_eq!(ghash_mul(&one, &x), x);
+}
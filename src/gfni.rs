@@ -0,0 +1,202 @@
+//! Bit reversal with the GFNI `gf2p8affineqb` instruction, which reverses the bits of every byte
+//! in a vector at once.
+//!
+//! GFNI is detected at runtime when the `use_std` feature is enabled on x86_64. Everywhere else,
+//! and on CPUs without GFNI, `GfniReverse` falls back to `ParallelReverse`.
+//!
+//! ```
+//! use bit_reverse::GfniReverse;
+//!
+//! let mut words = [0x0123456789ABCDEFu64; 4];
+//! GfniReverse::swap_bits_slice(&mut words);
+//! assert_eq!(words, [0xF7B3D591E6A2C480; 4]);
+//! ```
+
+#[cfg(all(feature = "use_std", target_arch = "x86_64"))]
+use core::arch::x86_64::*;
+
+/// Whether the CPU supports GFNI, in which case `GfniReverse` uses it.
+#[inline]
+pub fn is_available() -> bool {
+    #[cfg(all(feature = "use_std", target_arch = "x86_64"))]
+    {
+        is_x86_feature_detected!("gfni")
+    }
+    #[cfg(not(all(feature = "use_std", target_arch = "x86_64")))]
+    {
+        false
+    }
+}
+
+/// Computes bit reversal by reversing the bits of every byte with one GFNI affine transform, then
+/// swapping the byte order. Falls back to `ParallelReverse` when GFNI isn't available.
+///
+/// # Timing
+/// Branch-free and table-free once GFNI has been detected. Use `ConstTimeReverse` for secret
+/// values.
+pub trait GfniReverse {
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
+    fn swap_bits(self) -> Self;
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    fn swap_low_bits(self, bits: u32) -> Self;
+
+    /// Swaps the bits of every value in `values`. Unsigned types are transformed 16 bytes at a
+    /// time.
+    #[inline]
+    fn swap_bits_slice(values: &mut [Self])
+    where
+        Self: Sized + Copy,
+    {
+        for v in values.iter_mut() {
+            *v = v.swap_bits();
+        }
+    }
+}
+
+// The affine matrix that maps bit i of each byte to bit 7-i. Row j of the matrix, which computes
+// bit j of the result, is byte 7-j and selects bit 7-j of the input.
+#[cfg(all(feature = "use_std", target_arch = "x86_64"))]
+const REVERSE_MATRIX: i64 = 0x8040201008040201;
+
+#[cfg(all(feature = "use_std", target_arch = "x86_64"))]
+#[target_feature(enable = "gfni")]
+unsafe fn reverse_each_byte(v: u64) -> u64 {
+    let r = _mm_gf2p8affine_epi64_epi8(
+        _mm_cvtsi64_si128(v as i64),
+        _mm_set1_epi64x(REVERSE_MATRIX),
+        0,
+    );
+    _mm_cvtsi128_si64(r) as u64
+}
+
+#[cfg(all(feature = "use_std", target_arch = "x86_64"))]
+#[target_feature(enable = "gfni")]
+unsafe fn reverse_each_byte_slice(bytes: &mut [u8]) {
+    let matrix = _mm_set1_epi64x(REVERSE_MATRIX);
+    let mut chunks = bytes.chunks_exact_mut(16);
+    for chunk in &mut chunks {
+        let p = chunk.as_mut_ptr() as *mut __m128i;
+        _mm_storeu_si128(p, _mm_gf2p8affine_epi64_epi8(_mm_loadu_si128(p), matrix, 0));
+    }
+    for b in chunks.into_remainder() {
+        *b = reverse_each_byte(*b as u64) as u8;
+    }
+}
+
+macro_rules! doit_gfni { ($($ty:ty),*) => ($(
+    impl GfniReverse for $ty {
+        #[inline]
+        fn swap_bits(self) -> $ty {
+            #[cfg(all(feature = "use_std", target_arch = "x86_64"))]
+            {
+                if is_x86_feature_detected!("gfni") {
+                    return (unsafe { reverse_each_byte(self as u64) } as $ty).swap_bytes();
+                }
+            }
+            ::ParallelReverse::swap_bits(self)
+        }
+
+        swap_low_bits!(GfniReverse, $ty);
+
+        #[inline]
+        fn swap_bits_slice(values: &mut [$ty]) {
+            #[cfg(all(feature = "use_std", target_arch = "x86_64"))]
+            {
+                if is_x86_feature_detected!("gfni") {
+                    // Reversing the bits of each byte doesn't depend on how the bytes group into
+                    // values, so the whole slice can be transformed as bytes.
+                    let bytes = unsafe {
+                        ::core::slice::from_raw_parts_mut(
+                            values.as_mut_ptr() as *mut u8,
+                            ::core::mem::size_of_val(values),
+                        )
+                    };
+                    unsafe { reverse_each_byte_slice(bytes) };
+                    for v in values.iter_mut() {
+                        *v = v.swap_bytes();
+                    }
+                    return;
+                }
+            }
+            for v in values.iter_mut() {
+                *v = ::ParallelReverse::swap_bits(*v);
+            }
+        }
+    })*)
+}
+
+doit_gfni!(u8, u16, u32, u64, usize);
+
+#[cfg(feature = "u128")]
+impl GfniReverse for u128 {
+    #[inline]
+    fn swap_bits(self) -> u128 {
+        (GfniReverse::swap_bits(self as u64) as u128) << 64
+            | (GfniReverse::swap_bits((self >> 64) as u64) as u128)
+    }
+
+    swap_low_bits!(GfniReverse, u128);
+}
+
+doit_signed!(GfniReverse);
+test_suite!();
+
+#[cfg(test)]
+fn check_slice<T: GfniReverse + ::ParallelReverse + Copy + PartialEq + ::core::fmt::Debug>(
+    values: &mut [T],
+) {
+    let mut expected = [values[0]; 37];
+    for (e, &v) in expected.iter_mut().zip(values.iter()) {
+        *e = ::ParallelReverse::swap_bits(v);
+    }
+    GfniReverse::swap_bits_slice(values);
+    assert_eq!(values, &expected[..values.len()]);
+}
+
+#[test]
+fn slices_match_parallel() {
+    // Odd lengths leave a remainder after the 16 byte chunks.
+    let mut bytes = [0u8; 37];
+    let mut words = [0u32; 37];
+    let mut signed = [0i64; 37];
+    for (((b, w), s), x) in bytes
+        .iter_mut()
+        .zip(words.iter_mut())
+        .zip(signed.iter_mut())
+        .zip(::xorshift())
+    {
+        *b = x as u8;
+        *w = x as u32;
+        *s = x as i64;
+    }
+    check_slice(&mut bytes);
+    check_slice(&mut words[..33]);
+    check_slice(&mut signed);
+}
+
+// Exercises the GFNI path directly wherever it's available, since the tests above take the
+// fallback on other machines.
+#[cfg(all(feature = "use_std", target_arch = "x86_64"))]
+#[test]
+fn gfni_matches_parallel() {
+    if !is_available() {
+        return;
+    }
+    for x in ::xorshift().take(1000) {
+        let each_byte = unsafe { reverse_each_byte(x) };
+        assert_eq!(each_byte.swap_bytes(), ::ParallelReverse::swap_bits(x));
+    }
+    let mut bytes = [0u8; 256];
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    unsafe { reverse_each_byte_slice(&mut bytes) };
+    for (i, &b) in bytes.iter().enumerate() {
+        assert_eq!(b, ::ParallelReverse::swap_bits(i as u8));
+    }
+}
@@ -0,0 +1,280 @@
+//! Canonical Huffman codes for LSB-first formats like Deflate.
+//!
+//! Canonical codes are defined MSB-first, but Deflate and similar formats pack bits into bytes
+//! starting from the least significant bit. The codes built here are already reversed to their
+//! own length, so they can be written to an LSB-first bit stream as is.
+//!
+//! ```
+//! use bit_reverse::huffman::{canonical_codes, Code};
+//!
+//! // The example from RFC 1951 section 3.2.2.
+//! let lengths = [3, 3, 3, 3, 3, 2, 4, 4];
+//! let mut codes = [Code::default(); 8];
+//! canonical_codes(&lengths, &mut codes).unwrap();
+//!
+//! // Symbol F has the MSB-first code 00, and symbol G has 1110.
+//! assert_eq!(codes[5], Code { bits: 0b00, len: 2 });
+//! assert_eq!(codes[6], Code { bits: 0b0111, len: 4 });
+//! ```
+
+use core::fmt;
+use ParallelReverse;
+
+/// The longest code length supported.
+pub const MAX_CODE_LENGTH: u8 = 16;
+
+/// The reasons a set of code lengths cannot form a prefix code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A code length is longer than `MAX_CODE_LENGTH`.
+    LengthTooLong,
+    /// There are more codes of some length than the shorter codes leave room for.
+    Oversubscribed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::LengthTooLong => write!(f, "code length exceeds {} bits", MAX_CODE_LENGTH),
+            Error::Oversubscribed => write!(f, "code lengths are oversubscribed"),
+        }
+    }
+}
+
+#[cfg(feature = "use_std")]
+impl ::std::error::Error for Error {}
+
+/// A symbol's code, reversed so its first bit is the least significant bit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Code {
+    /// The code bits, to be written LSB-first.
+    pub bits: u16,
+    /// The length of the code in bits, or zero if the symbol has no code.
+    pub len: u8,
+}
+
+/// Assigns canonical codes to symbols with the given code lengths, where a length of zero means
+/// the symbol is unused. The code for `lengths[i]` is written to `codes[i]`.
+///
+/// Incomplete codes are accepted, since Deflate allows a single distance code.
+///
+/// # Panics
+/// Panics if `codes` is shorter than `lengths`.
+pub fn canonical_codes(lengths: &[u8], codes: &mut [Code]) -> Result<(), Error> {
+    assert!(
+        codes.len() >= lengths.len(),
+        "not enough room for every code"
+    );
+
+    let mut next_code = first_codes(lengths)?;
+    for (code, &len) in codes.iter_mut().zip(lengths.iter()) {
+        *code = if len == 0 {
+            Code::default()
+        } else {
+            let bits = next_code[len as usize];
+            next_code[len as usize] += 1;
+            Code {
+                bits: (bits as u16).swap_low_bits(len as u32),
+                len,
+            }
+        };
+    }
+    Ok(())
+}
+
+// Returns the first MSB-first code of every length, as in RFC 1951 section 3.2.2.
+fn first_codes(lengths: &[u8]) -> Result<[u32; MAX_CODE_LENGTH as usize + 1], Error> {
+    let mut count = [0u32; MAX_CODE_LENGTH as usize + 1];
+    for &len in lengths {
+        if len > MAX_CODE_LENGTH {
+            return Err(Error::LengthTooLong);
+        }
+        count[len as usize] += 1;
+    }
+    count[0] = 0;
+
+    let mut next_code = [0u32; MAX_CODE_LENGTH as usize + 1];
+    let mut code = 0;
+    for len in 1..=MAX_CODE_LENGTH as usize {
+        code = (code + count[len - 1]) << 1;
+        next_code[len] = code;
+        // Every code of this length must fit in len bits.
+        if code + count[len] > 1 << len {
+            return Err(Error::Oversubscribed);
+        }
+    }
+    Ok(next_code)
+}
+
+/// A single-level table for decoding codes built by `canonical_codes` from an LSB-first stream.
+#[cfg(feature = "use_std")]
+#[derive(Clone, Debug)]
+pub struct DecodeTable {
+    bits: u32,
+    // Each entry holds the symbol and code length for every index whose low bits match the code,
+    // with a zero length for indices no code matches.
+    entries: Vec<(usize, u8)>,
+}
+
+#[cfg(feature = "use_std")]
+impl DecodeTable {
+    /// Builds the decoding table for symbols with the given code lengths.
+    pub fn new(lengths: &[u8]) -> Result<DecodeTable, Error> {
+        let mut codes = vec![Code::default(); lengths.len()];
+        canonical_codes(lengths, &mut codes)?;
+
+        let bits = lengths.iter().cloned().max().unwrap_or(0) as u32;
+        let mut entries = vec![(0, 0); 1 << bits];
+        for (symbol, code) in codes.iter().enumerate() {
+            if code.len == 0 {
+                continue;
+            }
+            // Fill every index that starts with this code, whatever bits follow it.
+            let step = 1 << code.len;
+            let mut i = code.bits as usize;
+            while i < entries.len() {
+                entries[i] = (symbol, code.len);
+                i += step;
+            }
+        }
+        Ok(DecodeTable { bits, entries })
+    }
+
+    /// The number of bits to peek from the stream for `decode`, which is the longest code length.
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Decodes the symbol at the start of `peek`, which holds the next `bits` bits of the stream
+    /// with the first bit in its least significant bit. Returns the symbol and how many bits its
+    /// code used, or `None` if no code matches.
+    #[inline]
+    pub fn decode(&self, peek: u32) -> Option<(usize, u32)> {
+        let (symbol, len) = self.entries[(peek & ((1 << self.bits) - 1)) as usize];
+        if len == 0 {
+            None
+        } else {
+            Some((symbol, len as u32))
+        }
+    }
+}
+
+#[test]
+fn rfc1951_example() {
+    let lengths = [3, 3, 3, 3, 3, 2, 4, 4];
+    let mut codes = [Code::default(); 8];
+    canonical_codes(&lengths, &mut codes).unwrap();
+
+    let msb_first = [0b010, 0b011, 0b100, 0b101, 0b110, 0b00, 0b1110, 0b1111];
+    for ((code, &len), &expected) in codes.iter().zip(lengths.iter()).zip(msb_first.iter()) {
+        assert_eq!(code.len, len);
+        assert_eq!(code.bits.swap_low_bits(len as u32), expected);
+    }
+}
+
+#[test]
+fn deflate_fixed_literal_codes() {
+    let mut lengths = [0u8; 288];
+    for (i, len) in lengths.iter_mut().enumerate() {
+        *len = match i {
+            0..=143 => 8,
+            144..=255 => 9,
+            256..=279 => 7,
+            _ => 8,
+        };
+    }
+    let mut codes = [Code::default(); 288];
+    canonical_codes(&lengths, &mut codes).unwrap();
+
+    // Literal 0 is 00110000, 144 is 110010000, 256 is 0000000 and 280 is 11000000.
+    assert_eq!(
+        codes[0],
+        Code {
+            bits: 0b00001100,
+            len: 8
+        }
+    );
+    assert_eq!(
+        codes[144],
+        Code {
+            bits: 0b000010011,
+            len: 9
+        }
+    );
+    assert_eq!(codes[256], Code { bits: 0, len: 7 });
+    assert_eq!(
+        codes[280],
+        Code {
+            bits: 0b00000011,
+            len: 8
+        }
+    );
+}
+
+#[test]
+fn unused_and_single_codes() {
+    let mut codes = [Code::default(); 3];
+    canonical_codes(&[0, 1, 0], &mut codes).unwrap();
+    assert_eq!(
+        codes,
+        [Code::default(), Code { bits: 0, len: 1 }, Code::default()]
+    );
+}
+
+#[test]
+fn invalid_lengths() {
+    let mut codes = [Code::default(); 3];
+    assert_eq!(
+        canonical_codes(&[1, 1, 1], &mut codes),
+        Err(Error::Oversubscribed)
+    );
+    assert_eq!(
+        canonical_codes(&[1, 17, 1], &mut codes),
+        Err(Error::LengthTooLong)
+    );
+}
+
+#[cfg(feature = "use_std")]
+#[test]
+fn decode_table_round_trip() {
+    let lengths = [3, 3, 3, 3, 3, 2, 4, 4];
+    let mut codes = [Code::default(); 8];
+    canonical_codes(&lengths, &mut codes).unwrap();
+    let table = DecodeTable::new(&lengths).unwrap();
+    assert_eq!(table.bits(), 4);
+
+    // Pack a message LSB-first, then decode it back.
+    let message = [5usize, 0, 7, 6, 2, 5];
+    let mut stream = 0u64;
+    let mut len = 0;
+    for &symbol in message.iter() {
+        let code = codes[symbol];
+        stream |= (code.bits as u64) << len;
+        len += code.len as u32;
+    }
+    for &symbol in message.iter() {
+        let (decoded, used) = table.decode(stream as u32).unwrap();
+        assert_eq!(decoded, symbol);
+        stream >>= used;
+    }
+}
+
+#[cfg(feature = "use_std")]
+#[test]
+fn decode_table_incomplete_code() {
+    let table = DecodeTable::new(&[0, 2]).unwrap();
+    assert_eq!(table.decode(0b00), Some((1, 2)));
+    assert_eq!(table.decode(0b01), None);
+}
+
+#[cfg(feature = "use_std")]
+#[test]
+fn decode_table_large_symbols() {
+    // Unused symbols push the only codes past the range of a u16.
+    let mut lengths = vec![0u8; 70000];
+    lengths[65536] = 1;
+    lengths[69999] = 1;
+    let table = DecodeTable::new(&lengths).unwrap();
+    assert_eq!(table.decode(0b0), Some((65536, 1)));
+    assert_eq!(table.decode(0b1), Some((69999, 1)));
+}
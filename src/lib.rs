@@ -1,6 +1,11 @@
 //! ## Library Objective
 //! This library provides a number of ways to compute the bit reversal of all primitive integers.
-//! There are currently 3 different algorithms implemented: Bitwise, Parallel, and Lookup reversal.
+//! Each algorithm is a trait with the same `swap_bits` and `swap_low_bits` methods:
+//! `BitwiseReverse`, `LookupReverse`, `ParallelReverse`, `NibbleLookupReverse`,
+//! `NibbleRegisterReverse`, `WideLookupReverse`, `MultiplyReverse`, `GfniReverse`,
+//! `ConstTimeReverse`, and `NativeReverse`, which uses the standard library's `reverse_bits` so the
+//! algorithms can be compared against the compiler's own lowering. `AutoReverse` picks the fastest
+//! of them at runtime, and `SimdReverse` reverses whole SIMD registers.
 //!
 //! ## Example
 //! ```
@@ -12,6 +17,47 @@
 //! Then you can call `swap_bits`() on any primitive integer. If you want to try a different
 //! algorithm just change the use statement and now your program will use the algorithm instead.
 //!
+//! ## Generic Code
+//! Importing more than one algorithm trait makes `swap_bits` ambiguous. Generic code can instead
+//! bound on `BitReverse` and take the algorithm as a type parameter, named after its trait, such as
+//! `Lookup` for `LookupReverse`.
+//! ```
+//! use bit_reverse::{BitReverse, Lookup};
+//!
+//! assert_eq!(0xA0u8.reverse_bits_with::<Lookup>(), 0x05u8);
+//! ```
+//! With the `num-traits` feature, `PrimIntReverse` is implemented for every `num_traits::PrimInt`,
+//! and the `prim_int` module provides the generalized reverse `grev`, for code that is already
+//! generic over `PrimInt`.
+//!
+//! ## Default Algorithm
+//! `reverse` and `reverse_low_bits` use the algorithm picked by the `default-bitwise`,
+//! `default-lookup` or `default-parallel` feature, so a binary can switch algorithms without
+//! touching the libraries that call them. `default-bitwise` wins over `default-lookup`, and
+//! `ParallelReverse` is used if neither is enabled. `default-parallel` is a no-op that lets a
+//! crate state the fallback explicitly.
+//! ```
+//! assert_eq!(bit_reverse::reverse(0xA0u8), 0x05u8);
+//! ```
+//!
+//! ## Const Functions
+//! The `bitwise`, `lookup` and `parallel` modules provide each algorithm as `const fn`s, from
+//! `reverse_u8` to `reverse_u128` and their signed variants, for tables and registers computed at
+//! compile time. The `lookup` module also generates reversal tables of any power of two length
+//! and entry type, such as `reverse_table_u16::<4096>()` for 12 bit indices, and exports
+//! `REVERSE_LOOKUP` as the 256 entry instance.
+//! ```
+//! const POLY: u32 = bit_reverse::parallel::reverse_u32(0x04C11DB7);
+//! assert_eq!(POLY, 0xEDB88320);
+//! ```
+//!
+//! ## Macros
+//! The `bit_reverse_macros` companion crate provides `bitrev!`, which reverses an integer
+//! literal at compile time, such as `bitrev!(0xA0u8)` or `bitrev!(0b1011, width = 4)`. Its
+//! `#[derive(BitReverse)]` implements the bitwise, lookup and parallel algorithms for register
+//! newtypes, and for bitfield structs with `#[bits(N)]` fields by reversing their packed value.
+//! It depends on this crate, so add both to your Cargo.toml.
+//!
 //! ## YMMV Performance Comparison
 //! I wouldn't use `BitwiseReverse` as it is mainly there for completeness and is strictly inferior
 //! to `ParallelReverse`, which is a Bitwise Parallel Reverse and thus an order of magnitude faster.
@@ -22,16 +68,90 @@
 //! at 32 bits and much better at 64 bits. These runtime characteristics are based on a Intel(R)
 //! Core(TM) i7-4770K CPU @ 3.50GHz.
 //!
+//! ## Automatic Selection
+//! With `use_std`, `AutoReverse` measures every algorithm except `ConstTimeReverse` for each type
+//! on the current CPU the first time the type is reversed, and uses the fastest from then on. The
+//! choice can be pinned with `pin_backend`, or for every type with the `BIT_REVERSE_BACKEND`
+//! environment variable.
+//!
+//! ## Multiplication
+//! `MultiplyReverse` reverses each byte with the multiplication tricks from Bit Twiddling Hacks,
+//! which are branch-free and table-free and can be the fastest option on processors with a fast
+//! multiplier. The `multiply` module also provides each of the byte variants on their own.
+//!
+//! ## GFNI
+//! `GfniReverse` reverses the bits of every byte with one `gf2p8affineqb` instruction on x86_64
+//! CPUs with GFNI, detected at runtime with `use_std`, and falls back to `ParallelReverse`
+//! elsewhere. Its `swap_bits_slice` transforms whole slices 16 bytes at a time.
+//!
+//! ## SIMD Registers
+//! On x86, the `simd` module reverses `__m128i` and `__m256i` registers per 8, 16, 32 or 64 bit
+//! lane or as a whole, with `pshufb` nibble lookups, so SIMD kernels don't have to spill to memory.
+//! `SimdReverse` provides safe methods when the crate is compiled with `ssse3` or `avx2` enabled.
+//!
+//! ## Constant Time
+//! `ConstTimeReverse` is branch-free and table-free, with optimization barriers so the compiler
+//! can't undo that, for use on secret values in cryptographic code. `BitwiseReverse` and
+//! `LookupReverse` leak timing, and each trait documents its timing properties. The statistical
+//! timing tests run with `cargo test --release -- --ignored --test-threads=1 timing`.
+//!
 //! ## Memory Consumption
 //! `BitwiseReverse` and `ParallelReverse` both only use a couple of stack variables for their
 //! computations. `LookupReverse` on the other hand statically allocates 256 u8s or 256 bytes to
 //! do its computations. `LookupReverse`'s memory cost is shared by all of the types
 //! `LookupReverse` supports.
+//! `NibbleLookupReverse` needs only an 8 byte table, and `NibbleRegisterReverse` keeps its
+//! table in a constant, for targets where 256 bytes is too much but `BitwiseReverse` is too slow.
+//! `WideLookupReverse` goes the other way, with a 128 KiB table of 65536 `u16`s generated at
+//! compile time, so that a `u64` takes 4 lookups instead of 8.
+//! `TableReverse` does the same lookups as `LookupReverse` against a table you provide, so it
+//! can be placed in flash, tightly coupled memory or its own `#[link_section]`.
 //!
 //! ## `no_std` Compatible
 //! To link to core instead of STD, disable default features for this library in your Cargo.toml.
 //! [Cargo choosing features](http://doc.crates.io/specifying-dependencies.html#choosing-features)
 //!
+//! ## Partial Reversal
+//! Every algorithm also provides `swap_low_bits`(n), which reverses only the lowest n bits of a
+//! value. This is what reflected CRCs and LSB-first codes of arbitrary length need.
+//!
+//! ## Bit Streams
+//! The `bitio` module reads and writes n-bit fields in LSB-first or MSB-first bit streams, over
+//! byte slices or, with `use_std`, `io::Read` and `io::Write`.
+//!
+//! ## Slices and Buffers
+//! `swap_bits_slice` reverses every integer in a slice, and `swap_bits_in_buffer` reverses every
+//! integer packed into a raw byte buffer, such as a DMA buffer of big-endian words.
+//!
+//! ## Stream Bytes
+//! `StreamBytes` converts every primitive integer to and from bytes in either byte order, with the
+//! bits of each byte in either order, such as `to_lsb_first_bytes` for LSB-first serial protocols.
+//!
+//! ## CRC
+//! The `crc` module provides table-driven and slice-by-8 CRC engines configured with the CRC
+//! catalogue's parameters, along with presets for common algorithms.
+//!
+//! ## GF(2) Polynomials
+//! The `gf2` module provides reciprocal polynomials, conversion between Fibonacci and Galois LFSR
+//! forms, and carry-less multiplication including GHASH's bit-reflected field.
+//!
+//! ## Huffman Codes
+//! The `huffman` module builds canonical Huffman codes that are already reversed for LSB-first
+//! formats like Deflate, along with a table for decoding them.
+//!
+//! ## PRBS
+//! The `prbs` module generates and checks the ITU-T O.150 PRBS patterns, packing bits LSB-first or
+//! MSB-first into bytes or words.
+//!
+//! ## Bit Numbering and Signals
+//! The `signal` module converts bit indices between LSB0 and MSB0 numbering, and extracts and
+//! inserts Intel and Motorola signals in byte arrays the way CAN DBC files describe them.
+//!
+//! ## Big Integers
+//! With the `num-bigint` feature, `BigReverse` reverses a `BigUint` to an explicit width, since a
+//! big integer's leading zeros aren't stored, and a `BigInt` as its two's complement in that width.
+//! The limbs are reversed in order and each limb with `ParallelReverse`.
+//!
 //! ## 128bit support
 //! You can enable support for `u128` and `i128` by enabling the feature `u128`.
 
@@ -42,13 +162,79 @@
 #[cfg(feature = "use_std")]
 extern crate std as core;
 
+#[cfg(feature = "num-bigint")]
+extern crate num_bigint;
+#[cfg(feature = "num-traits")]
+extern crate num_traits;
+
 #[macro_use]
 mod macros;
 
-mod bitwise;
-mod lookup;
-mod parallel;
+#[cfg(feature = "use_std")]
+mod auto;
+mod const_time;
+mod native;
+mod nibble;
+mod nibble_register;
+mod order;
+mod slice;
+mod strategy;
+mod stream;
+mod wide_lookup;
 
+#[cfg(feature = "num-bigint")]
+pub mod bigint;
+pub mod bitio;
+pub mod bitwise;
+pub mod crc;
+pub mod gf2;
+pub mod gfni;
+pub mod huffman;
+pub mod lookup;
+pub mod multiply;
+pub mod parallel;
+pub mod prbs;
+#[cfg(feature = "num-traits")]
+pub mod prim_int;
+pub mod signal;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub mod simd;
+
+#[cfg(feature = "use_std")]
+pub use auto::{AutoReverse, Backend, BACKEND_VAR};
+#[cfg(feature = "num-bigint")]
+pub use bigint::BigReverse;
 pub use bitwise::BitwiseReverse;
-pub use lookup::LookupReverse;
+pub use const_time::ConstTimeReverse;
+pub use gfni::GfniReverse;
+pub use lookup::{LookupReverse, TableReverse};
+pub use multiply::MultiplyReverse;
+pub use native::NativeReverse;
+pub use nibble::NibbleLookupReverse;
+pub use nibble_register::NibbleRegisterReverse;
+pub use order::{BigEndian, BitOrder, Endian, LittleEndian, NativeEndian};
 pub use parallel::ParallelReverse;
+#[cfg(feature = "num-traits")]
+pub use prim_int::PrimIntReverse;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub use simd::SimdReverse;
+pub use slice::{swap_bits_in_buffer, swap_bits_slice};
+pub use strategy::{
+    reverse, reverse_low_bits, BitReverse, Bitwise, ConstTime, DefaultStrategy, Gfni, Lookup,
+    Multiply, Native, NibbleLookup, NibbleRegister, Parallel, Strategy, WideLookup,
+};
+pub use stream::StreamBytes;
+pub use wide_lookup::WideLookupReverse;
+
+// An endless xorshift sequence, so tests and the auto-tuner can cover many inputs without a random
+// number generator.
+#[cfg(any(test, feature = "use_std"))]
+fn xorshift() -> impl Iterator<Item = u64> {
+    let mut x = 0x0123456789ABCDEFu64;
+    core::iter::repeat_with(move || {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        x
+    })
+}
@@ -1,120 +1,358 @@
+//! The lookup table algorithm, along with `const fn` versions of it for every primitive integer
+//! and a `const fn` generator for reversal tables of other sizes.
+//!
+//! ```
+//! use bit_reverse::lookup::reverse_u32;
+//!
+//! const REFLECTED_POLY: u32 = reverse_u32(0x04C11DB7);
+//! assert_eq!(REFLECTED_POLY, 0xEDB88320);
+//! ```
+//!
+//! The table generators are `reverse_table_u8` through `reverse_table_u64` (and `u128`), one per
+//! entry type, and take the table length rather than the index width. A signature like
+//! `reverse_table::<const BITS: usize, T>() -> [T; 1 << BITS]` needs generic const expressions,
+//! which aren't available on stable Rust. The length is checked during const evaluation instead,
+//! so a length that isn't a power of two fails to compile.
+//!
+//! ```
+//! use bit_reverse::lookup::reverse_table_u16;
+//!
+//! const REVERSE_12: [u16; 4096] = reverse_table_u16::<4096>();
+//! assert_eq!(REVERSE_12[1], 0x800);
+//! ```
+//!
+//! ```compile_fail
+//! use bit_reverse::lookup::reverse_table_u16;
+//!
+//! const REVERSE_12: [u16; 12] = reverse_table_u16::<12>();
+//! assert_eq!(REVERSE_12[1], 0x800);
+//! ```
+
 /// Computes bit reversal by using lookup table to translate a single byte into its reverse.
 /// For multi-byte types, the byte order is swapped to complete the reversal.
+///
+/// # Timing
+/// Not constant time. The table is indexed by the bytes of the value, so the running time can
+/// leak the value through the cache.
 pub trait LookupReverse {
     /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
     fn swap_bits(self) -> Self;
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    fn swap_low_bits(self, bits: u32) -> Self;
 }
 
-#[cfg_attr(rustfmt, rustfmt_skip)]
-const REVERSE_LOOKUP: [u8; 256] = [
-    0,  128, 64, 192, 32, 160,  96, 224, 16, 144, 80, 208, 48, 176, 112, 240,
-    8,  136, 72, 200, 40, 168, 104, 232, 24, 152, 88, 216, 56, 184, 120, 248,
-    4,  132, 68, 196, 36, 164, 100, 228, 20, 148, 84, 212, 52, 180, 116, 244,
-    12, 140, 76, 204, 44, 172, 108, 236, 28, 156, 92, 220, 60, 188, 124, 252,
-    2,  130, 66, 194, 34, 162,  98, 226, 18, 146, 82, 210, 50, 178, 114, 242,
-    10, 138, 74, 202, 42, 170, 106, 234, 26, 154, 90, 218, 58, 186, 122, 250,
-    6,  134, 70, 198, 38, 166, 102, 230, 22, 150, 86, 214, 54, 182, 118, 246,
-    14, 142, 78, 206, 46, 174, 110, 238, 30, 158, 94, 222, 62, 190, 126, 254,
-    1,  129, 65, 193, 33, 161,  97, 225, 17, 145, 81, 209, 49, 177, 113, 241,
-    9,  137, 73, 201, 41, 169, 105, 233, 25, 153, 89, 217, 57, 185, 121, 249,
-    5,  133, 69, 197, 37, 165, 101, 229, 21, 149, 85, 213, 53, 181, 117, 245,
-    13, 141, 77, 205, 45, 173, 109, 237, 29, 157, 93, 221, 61, 189, 125, 253,
-    3,  131, 67, 195, 35, 163,  99, 227, 19, 147, 83, 211, 51, 179, 115, 243,
-    11, 139, 75, 203, 43, 171, 107, 235, 27, 155, 91, 219, 59, 187, 123, 251,
-    7,  135, 71, 199, 39, 167, 103, 231, 23, 151, 87, 215, 55, 183, 119, 247,
-    15, 143, 79, 207, 47, 175, 111, 239, 31, 159, 95, 223, 63, 191, 127, 255
-];
-
-impl LookupReverse for u8 {
-    #[inline]
-    fn swap_bits(self) -> u8 {
-        REVERSE_LOOKUP[self as usize]
-    }
+/// Every byte's reverse, indexed by the byte.
+pub const REVERSE_LOOKUP: [u8; 256] = reverse_table_u8::<256>();
+
+macro_rules! doit_table { ($($name:ident: $ty:ty),*) => ($(
+    /// Generates a reversal table with `LEN` entries, where `LEN` is a power of two and entry i is
+    /// i with its low log2(`LEN`) bits reversed. A 16 entry table reverses nibbles and a 4096
+    /// entry table reverses 12 bit values.
+    ///
+    /// The parameter is the table length, not the index width, because a `[T; 1 << BITS]` return
+    /// type can't be written on stable Rust.
+    ///
+    /// # Panics
+    /// Panics, at compile time when used in a constant, if `LEN` isn't a power of two or the index
+    /// has more bits than the entries hold.
+    pub const fn $name<const LEN: usize>() -> [$ty; LEN] {
+        assert!(LEN.is_power_of_two(), "the table length must be a power of two");
+        let bits = LEN.trailing_zeros();
+        assert!(
+            bits as usize <= 8 * ::core::mem::size_of::<$ty>(),
+            "the table entries are too narrow for the index"
+        );
+
+        let mut table = [0; LEN];
+        let mut i = 0;
+        // A single entry table has no bits to reverse, and the shift would overflow.
+        while bits > 0 && i < LEN {
+            table[i] = (::parallel::reverse_u64(i as u64) >> (64 - bits)) as $ty;
+            i += 1;
+        }
+        table
+    })*)
 }
 
-impl LookupReverse for u16 {
-    #[inline]
-    fn swap_bits(self) -> u16 {
-        (REVERSE_LOOKUP[self as u8 as usize] as u16) << 8
-            | REVERSE_LOOKUP[(self >> 8) as u8 as usize] as u16
-    }
+doit_table!(
+    reverse_table_u8: u8,
+    reverse_table_u16: u16,
+    reverse_table_u32: u32,
+    reverse_table_u64: u64
+);
+#[cfg(feature = "u128")]
+doit_table!(reverse_table_u128: u128);
+
+/// Swaps the bits such that bit i is now bit N-i, where N is the length of the type.
+#[inline]
+pub const fn reverse_u8(v: u8) -> u8 {
+    REVERSE_LOOKUP[v as usize]
 }
 
-impl LookupReverse for u32 {
-    #[inline]
-    fn swap_bits(self) -> u32 {
-        (REVERSE_LOOKUP[self as u8 as usize] as u32) << 24
-            | (REVERSE_LOOKUP[(self >> 8) as u8 as usize] as u32) << 16
-            | (REVERSE_LOOKUP[(self >> 16) as u8 as usize] as u32) << 8
-            | (REVERSE_LOOKUP[(self >> 24) as u8 as usize] as u32)
-    }
+/// Swaps the bits such that bit i is now bit N-i, where N is the length of the type.
+#[inline]
+pub const fn reverse_u16(v: u16) -> u16 {
+    (REVERSE_LOOKUP[v as u8 as usize] as u16) << 8 | REVERSE_LOOKUP[(v >> 8) as u8 as usize] as u16
 }
 
-impl LookupReverse for u64 {
-    #[inline]
-    fn swap_bits(self) -> u64 {
-        (REVERSE_LOOKUP[self as u8 as usize] as u64) << 56
-            | (REVERSE_LOOKUP[(self >> 8) as u8 as usize] as u64) << 48
-            | (REVERSE_LOOKUP[(self >> 16) as u8 as usize] as u64) << 40
-            | (REVERSE_LOOKUP[(self >> 24) as u8 as usize] as u64) << 32
-            | (REVERSE_LOOKUP[(self >> 32) as u8 as usize] as u64) << 24
-            | (REVERSE_LOOKUP[(self >> 40) as u8 as usize] as u64) << 16
-            | (REVERSE_LOOKUP[(self >> 48) as u8 as usize] as u64) << 8
-            | (REVERSE_LOOKUP[(self >> 56) as u8 as usize] as u64)
-    }
+/// Swaps the bits such that bit i is now bit N-i, where N is the length of the type.
+#[inline]
+pub const fn reverse_u32(v: u32) -> u32 {
+    (REVERSE_LOOKUP[v as u8 as usize] as u32) << 24
+        | (REVERSE_LOOKUP[(v >> 8) as u8 as usize] as u32) << 16
+        | (REVERSE_LOOKUP[(v >> 16) as u8 as usize] as u32) << 8
+        | (REVERSE_LOOKUP[(v >> 24) as u8 as usize] as u32)
+}
+
+/// Swaps the bits such that bit i is now bit N-i, where N is the length of the type.
+#[inline]
+pub const fn reverse_u64(v: u64) -> u64 {
+    (REVERSE_LOOKUP[v as u8 as usize] as u64) << 56
+        | (REVERSE_LOOKUP[(v >> 8) as u8 as usize] as u64) << 48
+        | (REVERSE_LOOKUP[(v >> 16) as u8 as usize] as u64) << 40
+        | (REVERSE_LOOKUP[(v >> 24) as u8 as usize] as u64) << 32
+        | (REVERSE_LOOKUP[(v >> 32) as u8 as usize] as u64) << 24
+        | (REVERSE_LOOKUP[(v >> 40) as u8 as usize] as u64) << 16
+        | (REVERSE_LOOKUP[(v >> 48) as u8 as usize] as u64) << 8
+        | (REVERSE_LOOKUP[(v >> 56) as u8 as usize] as u64)
 }
 
+/// Swaps the bits such that bit i is now bit N-i, where N is the length of the type.
 #[cfg(feature = "u128")]
-impl LookupReverse for u128 {
-    #[inline]
-    fn swap_bits(self) -> u128 {
-        (REVERSE_LOOKUP[self as u8 as usize] as u128) << 120
-            | (REVERSE_LOOKUP[(self >> 8) as u8 as usize] as u128) << 112
-            | (REVERSE_LOOKUP[(self >> 16) as u8 as usize] as u128) << 104
-            | (REVERSE_LOOKUP[(self >> 24) as u8 as usize] as u128) << 96
-            | (REVERSE_LOOKUP[(self >> 32) as u8 as usize] as u128) << 88
-            | (REVERSE_LOOKUP[(self >> 40) as u8 as usize] as u128) << 80
-            | (REVERSE_LOOKUP[(self >> 48) as u8 as usize] as u128) << 72
-            | (REVERSE_LOOKUP[(self >> 56) as u8 as usize] as u128) << 64
-            | (REVERSE_LOOKUP[(self >> 64) as u8 as usize] as u128) << 56
-            | (REVERSE_LOOKUP[(self >> 72) as u8 as usize] as u128) << 48
-            | (REVERSE_LOOKUP[(self >> 80) as u8 as usize] as u128) << 40
-            | (REVERSE_LOOKUP[(self >> 88) as u8 as usize] as u128) << 32
-            | (REVERSE_LOOKUP[(self >> 96) as u8 as usize] as u128) << 24
-            | (REVERSE_LOOKUP[(self >> 104) as u8 as usize] as u128) << 16
-            | (REVERSE_LOOKUP[(self >> 112) as u8 as usize] as u128) << 8
-            | (REVERSE_LOOKUP[(self >> 120) as u8 as usize] as u128)
-    }
+#[inline]
+pub const fn reverse_u128(v: u128) -> u128 {
+    (REVERSE_LOOKUP[v as u8 as usize] as u128) << 120
+        | (REVERSE_LOOKUP[(v >> 8) as u8 as usize] as u128) << 112
+        | (REVERSE_LOOKUP[(v >> 16) as u8 as usize] as u128) << 104
+        | (REVERSE_LOOKUP[(v >> 24) as u8 as usize] as u128) << 96
+        | (REVERSE_LOOKUP[(v >> 32) as u8 as usize] as u128) << 88
+        | (REVERSE_LOOKUP[(v >> 40) as u8 as usize] as u128) << 80
+        | (REVERSE_LOOKUP[(v >> 48) as u8 as usize] as u128) << 72
+        | (REVERSE_LOOKUP[(v >> 56) as u8 as usize] as u128) << 64
+        | (REVERSE_LOOKUP[(v >> 64) as u8 as usize] as u128) << 56
+        | (REVERSE_LOOKUP[(v >> 72) as u8 as usize] as u128) << 48
+        | (REVERSE_LOOKUP[(v >> 80) as u8 as usize] as u128) << 40
+        | (REVERSE_LOOKUP[(v >> 88) as u8 as usize] as u128) << 32
+        | (REVERSE_LOOKUP[(v >> 96) as u8 as usize] as u128) << 24
+        | (REVERSE_LOOKUP[(v >> 104) as u8 as usize] as u128) << 16
+        | (REVERSE_LOOKUP[(v >> 112) as u8 as usize] as u128) << 8
+        | (REVERSE_LOOKUP[(v >> 120) as u8 as usize] as u128)
 }
 
-impl LookupReverse for usize {
-    #[inline]
+/// Swaps the bits such that bit i is now bit N-i, where N is the length of the type.
+#[inline]
+pub const fn reverse_usize(v: usize) -> usize {
     #[cfg(target_pointer_width = "8")]
-    fn swap_bits(self) -> usize {
-        use LookupReverse;
-        LookupReverse::swap_bits(self as u8) as usize
+    {
+        reverse_u8(v as u8) as usize
     }
-
-    #[inline]
     #[cfg(target_pointer_width = "16")]
-    fn swap_bits(self) -> usize {
-        use LookupReverse;
-        LookupReverse::swap_bits(self as u16) as usize
+    {
+        reverse_u16(v as u16) as usize
+    }
+    #[cfg(target_pointer_width = "32")]
+    {
+        reverse_u32(v as u32) as usize
+    }
+    #[cfg(target_pointer_width = "64")]
+    {
+        reverse_u64(v as u64) as usize
     }
+}
+
+macro_rules! doit_lookup { ($($name:ident: $ty:ty),*) => ($(
+    impl LookupReverse for $ty {
+        #[inline]
+        fn swap_bits(self) -> $ty {
+            $name(self)
+        }
+
+        swap_low_bits!(LookupReverse, $ty);
+    })*)
+}
 
+doit_lookup!(
+    reverse_u8: u8,
+    reverse_u16: u16,
+    reverse_u32: u32,
+    reverse_u64: u64,
+    reverse_usize: usize
+);
+#[cfg(feature = "u128")]
+doit_lookup!(reverse_u128: u128);
+const_signed!();
+doit_signed!(LookupReverse);
+
+/// Reverses bits with a caller-provided 256 entry table in place of `REVERSE_LOOKUP`, so the table
+/// can live wherever lookups are fastest, such as flash, tightly coupled memory or a
+/// `#[link_section]`.
+///
+/// ```
+/// use bit_reverse::lookup::reverse_table_u8;
+/// use bit_reverse::TableReverse;
+///
+/// static TABLE: [u8; 256] = reverse_table_u8::<256>();
+///
+/// let reverser = TableReverse::new(&TABLE);
+/// assert_eq!(reverser.swap_bits(0xABCDu16), 0xB3D5u16);
+/// assert_eq!(reverser.swap_low_bits(0b1011u8, 4), 0b1101u8);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TableReverse<'a> {
+    table: &'a [u8; 256],
+}
+
+impl<'a> TableReverse<'a> {
+    /// Creates a reverser that looks up each byte's reverse in `table`.
+    pub const fn new(table: &'a [u8; 256]) -> TableReverse<'a> {
+        TableReverse { table }
+    }
+
+    /// Returns the table the reverser looks bytes up in.
+    pub const fn table(&self) -> &'a [u8; 256] {
+        self.table
+    }
+
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
     #[inline]
-    #[cfg(target_pointer_width = "32")]
-    fn swap_bits(self) -> usize {
-        use LookupReverse;
-        LookupReverse::swap_bits(self as u32) as usize
+    pub fn swap_bits<T: TableReversible>(&self, value: T) -> T {
+        value.swap_bits_with_table(self.table)
     }
 
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
     #[inline]
-    #[cfg(target_pointer_width = "64")]
-    fn swap_bits(self) -> usize {
-        LookupReverse::swap_bits(self as u64) as usize
+    pub fn swap_low_bits<T: TableReversible>(&self, value: T, bits: u32) -> T {
+        value.swap_low_bits_with_table(self.table, bits)
     }
 }
 
-doit_signed!(LookupReverse);
+impl Default for TableReverse<'static> {
+    /// A reverser over the crate's own `REVERSE_LOOKUP`.
+    fn default() -> TableReverse<'static> {
+        TableReverse::new(&REVERSE_LOOKUP)
+    }
+}
+
+/// Integers that `TableReverse` can reverse, by looking up each of their bytes in a table.
+pub trait TableReversible: Sized {
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
+    fn swap_bits_with_table(self, table: &[u8; 256]) -> Self;
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    fn swap_low_bits_with_table(self, table: &[u8; 256], bits: u32) -> Self;
+}
+
+macro_rules! doit_table_reverse { ($($ty:ty => $uty:ty),*) => ($(
+    impl TableReversible for $ty {
+        #[inline]
+        fn swap_bits_with_table(self, table: &[u8; 256]) -> $ty {
+            let mut bytes = (self as $uty).to_le_bytes();
+            for b in bytes.iter_mut() {
+                *b = table[*b as usize];
+            }
+            <$uty>::from_be_bytes(bytes) as $ty
+        }
+
+        #[inline]
+        fn swap_low_bits_with_table(self, table: &[u8; 256], bits: u32) -> $ty {
+            let width = 8 * ::core::mem::size_of::<$ty>() as u32;
+            assert!(
+                bits <= width,
+                "cannot reverse more bits than the type holds"
+            );
+
+            if bits == 0 {
+                0
+            } else {
+                ((self.swap_bits_with_table(table) as $uty) >> (width - bits)) as $ty
+            }
+        }
+    })*)
+}
+
+doit_table_reverse!(
+    u8 => u8,
+    u16 => u16,
+    u32 => u32,
+    u64 => u64,
+    usize => usize,
+    i8 => u8,
+    i16 => u16,
+    i32 => u32,
+    i64 => u64,
+    isize => usize
+);
+#[cfg(feature = "u128")]
+doit_table_reverse!(u128 => u128, i128 => u128);
+
 test_suite!();
+const_test_suite!();
+
+#[test]
+fn generated_tables() {
+    const NIBBLES: [u8; 16] = reverse_table_u8::<16>();
+    const TWELVE_BITS: [u16; 4096] = reverse_table_u16::<4096>();
+    assert_eq!(reverse_table_u32::<1>(), [0]);
+    assert_eq!(reverse_table_u64::<2>(), [0, 1]);
+    assert_eq!(NIBBLES[0b0001], 0b1000);
+    assert_eq!(NIBBLES[0b1011], 0b1101);
+    for (i, &r) in TWELVE_BITS.iter().enumerate() {
+        assert_eq!(r, (i as u16).swap_low_bits(12));
+    }
+
+    assert_eq!(REVERSE_LOOKUP[0x01], 0x80);
+    assert_eq!(REVERSE_LOOKUP[0x0F], 0xF0);
+    assert_eq!(REVERSE_LOOKUP[0xAB], 0xD5);
+    for (i, &r) in REVERSE_LOOKUP.iter().enumerate() {
+        assert_eq!(REVERSE_LOOKUP[r as usize] as usize, i);
+    }
+}
+
+#[test]
+fn table_reverse_matches_lookup() {
+    let reverser = TableReverse::default();
+    for x in ::xorshift().take(1000) {
+        assert_eq!(reverser.swap_bits(x), x.swap_bits());
+        assert_eq!(reverser.swap_bits(x as u8), (x as u8).swap_bits());
+        assert_eq!(reverser.swap_bits(x as i32), (x as i32).swap_bits());
+        assert_eq!(
+            reverser.swap_low_bits(x as u16, 12),
+            (x as u16).swap_low_bits(12)
+        );
+        assert_eq!(
+            reverser.swap_low_bits(x as i16, 3),
+            (x as i16).swap_low_bits(3)
+        );
+    }
+}
+
+#[test]
+fn table_reverse_uses_the_table() {
+    // With an identity table only the byte order changes.
+    static IDENTITY: [u8; 256] = {
+        let mut table = [0; 256];
+        let mut i = 0;
+        while i < 256 {
+            table[i] = i as u8;
+            i += 1;
+        }
+        table
+    };
+    let reverser = TableReverse::new(&IDENTITY);
+    assert!(::core::ptr::eq(reverser.table(), &IDENTITY));
+    assert_eq!(reverser.swap_bits(0x01234567u32), 0x67452301u32);
+    assert_eq!(reverser.swap_bits(0xABu8), 0xABu8);
+}
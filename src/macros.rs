@@ -5,6 +5,11 @@ macro_rules! doit_signed {
             fn swap_bits(self) -> i8 {
                 $Algo::swap_bits(self as u8) as i8
             }
+
+            #[inline]
+            fn swap_low_bits(self, bits: u32) -> i8 {
+                $Algo::swap_low_bits(self as u8, bits) as i8
+            }
         }
 
         impl $Algo for i16 {
@@ -12,6 +17,11 @@ macro_rules! doit_signed {
             fn swap_bits(self) -> i16 {
                 $Algo::swap_bits(self as u16) as i16
             }
+
+            #[inline]
+            fn swap_low_bits(self, bits: u32) -> i16 {
+                $Algo::swap_low_bits(self as u16, bits) as i16
+            }
         }
 
         impl $Algo for i32 {
@@ -19,6 +29,11 @@ macro_rules! doit_signed {
             fn swap_bits(self) -> i32 {
                 $Algo::swap_bits(self as u32) as i32
             }
+
+            #[inline]
+            fn swap_low_bits(self, bits: u32) -> i32 {
+                $Algo::swap_low_bits(self as u32, bits) as i32
+            }
         }
 
         impl $Algo for i64 {
@@ -26,6 +41,11 @@ macro_rules! doit_signed {
             fn swap_bits(self) -> i64 {
                 $Algo::swap_bits(self as u64) as i64
             }
+
+            #[inline]
+            fn swap_low_bits(self, bits: u32) -> i64 {
+                $Algo::swap_low_bits(self as u64, bits) as i64
+            }
         }
 
         #[cfg(feature = "u128")]
@@ -34,6 +54,11 @@ macro_rules! doit_signed {
             fn swap_bits(self) -> i128 {
                 $Algo::swap_bits(self as u128) as i128
             }
+
+            #[inline]
+            fn swap_low_bits(self, bits: u32) -> i128 {
+                $Algo::swap_low_bits(self as u128, bits) as i128
+            }
         }
 
         impl $Algo for isize {
@@ -41,78 +66,219 @@ macro_rules! doit_signed {
             fn swap_bits(self) -> isize {
                 $Algo::swap_bits(self as usize) as isize
             }
+
+            #[inline]
+            fn swap_low_bits(self, bits: u32) -> isize {
+                $Algo::swap_low_bits(self as usize, bits) as isize
+            }
         }
     )*)
 }
 
-macro_rules! test_suite {
+macro_rules! const_signed {
     () => {
-        #[test]
-        fn reverse_u8() {
-            assert_eq!(0xABu8.swap_bits(), 0xD5u8);
+        /// Swaps the bits such that bit i is now bit N-i, where N is the length of the type.
+        #[inline]
+        pub const fn reverse_i8(v: i8) -> i8 {
+            reverse_u8(v as u8) as i8
         }
 
-        #[test]
-        fn reverse_u16() {
-            assert_eq!(0xABCDu16.swap_bits(), 0xB3D5u16);
+        /// Swaps the bits such that bit i is now bit N-i, where N is the length of the type.
+        #[inline]
+        pub const fn reverse_i16(v: i16) -> i16 {
+            reverse_u16(v as u16) as i16
         }
 
-        #[test]
-        fn reverse_u32() {
-            assert_eq!(0xABCD2345u32.swap_bits(), 0xA2C4B3D5u32);
+        /// Swaps the bits such that bit i is now bit N-i, where N is the length of the type.
+        #[inline]
+        pub const fn reverse_i32(v: i32) -> i32 {
+            reverse_u32(v as u32) as i32
         }
 
-        #[test]
-        fn reverse_u64() {
-            assert_eq!(0x0123456789ABCDEFu64.swap_bits(), 0xF7B3D591E6A2C480u64);
+        /// Swaps the bits such that bit i is now bit N-i, where N is the length of the type.
+        #[inline]
+        pub const fn reverse_i64(v: i64) -> i64 {
+            reverse_u64(v as u64) as i64
         }
 
+        /// Swaps the bits such that bit i is now bit N-i, where N is the length of the type.
         #[cfg(feature = "u128")]
-        #[test]
-        fn reverse_u128() {
-            assert_eq!(
-                0x0123456789ABCDEF0123456789ABCDEFu128.swap_bits(),
-                0xF7B3D591E6A2C480F7B3D591E6A2C480u128
-            );
+        #[inline]
+        pub const fn reverse_i128(v: i128) -> i128 {
+            reverse_u128(v as u128) as i128
         }
 
-        #[test]
-        fn reverse_usize() {
-            assert_eq!(0xFFusize.swap_bits(), 0xFFusize.swap_bytes());
-        }
-
-        #[test]
-        fn reverse_i8() {
-            assert_eq!(0xABi8.swap_bits(), 0xD5i8);
+        /// Swaps the bits such that bit i is now bit N-i, where N is the length of the type.
+        #[inline]
+        pub const fn reverse_isize(v: isize) -> isize {
+            reverse_usize(v as usize) as isize
         }
+    };
+}
 
+macro_rules! const_test_suite {
+    () => {
         #[test]
-        fn reverse_i16() {
-            assert_eq!(0xABCDi16.swap_bits(), 0xB3D5i16);
+        fn const_fns() {
+            const REGISTER: u32 = reverse_u32(0x04C11DB7);
+            const TABLE: [u8; 4] = [reverse_u8(1), reverse_u8(2), reverse_u8(3), reverse_u8(4)];
+            assert_eq!(REGISTER, 0xEDB88320);
+            assert_eq!(TABLE, [0x80, 0x40, 0xC0, 0x20]);
+            assert_eq!(reverse_i16(0xABCDi16), 0xABCDi16.swap_bits());
+            assert_eq!(reverse_usize(1), 1usize.swap_bits());
         }
+    };
+}
 
-        #[test]
-        fn reverse_i32() {
-            assert_eq!(0xABCD2345i32.swap_bits(), 0xA2C4B3D5i32);
-        }
+macro_rules! doit_nibble {
+    ($Algo:ident, $reverse_nibble:path; $($ty:ty),*) => ($(
+        impl $Algo for $ty {
+            // The lowest nibble is reversed and pushed in first, so by the end every reversed
+            // nibble has been shifted up to the mirrored position.
+            #[inline]
+            fn swap_bits(self) -> $ty {
+                let mut v = self;
+                let mut r: $ty = 0;
+                for _ in 0..2 * ::core::mem::size_of::<$ty>() {
+                    r = (r << 4) | $reverse_nibble(v as u8 & 0xF) as $ty;
+                    v >>= 4;
+                }
+                r
+            }
 
-        #[test]
-        fn reverse_i64() {
-            assert_eq!(0x0123456789ABCDEFi64.swap_bits(), 0xF7B3D591E6A2C480i64);
+            swap_low_bits!($Algo, $ty);
         }
+    )*)
+}
 
-        #[cfg(feature = "u128")]
-        #[test]
-        fn reverse_i128() {
-            assert_eq!(
-                0x0123456789ABCDEF0123456789ABCDEFi128.swap_bits(),
-                0xF7B3D591E6A2C480F7B3D591E6A2C480i128
+macro_rules! swap_low_bits {
+    ($Algo:ident, $ty:ty) => {
+        #[inline]
+        fn swap_low_bits(self, bits: u32) -> $ty {
+            let width = 8 * ::core::mem::size_of::<$ty>() as u32;
+            assert!(
+                bits <= width,
+                "cannot reverse more bits than the type holds"
             );
+
+            // Reverse the whole value and shift the reversed low bits back down. The shift would
+            // overflow when no bits are reversed, so that case is handled separately.
+            if bits == 0 {
+                0
+            } else {
+                $Algo::swap_bits(self) >> (width - bits)
+            }
         }
+    };
+}
 
-        #[test]
-        fn reverse_isize() {
-            assert_eq!(0xFFisize.swap_bits(), 0xFFisize.swap_bytes());
+macro_rules! test_suite {
+    () => {
+        // A module of its own keeps the test names apart from the `const fn`s of the same names.
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            #[test]
+            fn reverse_u8() {
+                assert_eq!(0xABu8.swap_bits(), 0xD5u8);
+            }
+
+            #[test]
+            fn reverse_u16() {
+                assert_eq!(0xABCDu16.swap_bits(), 0xB3D5u16);
+            }
+
+            #[test]
+            fn reverse_u32() {
+                assert_eq!(0xABCD2345u32.swap_bits(), 0xA2C4B3D5u32);
+            }
+
+            #[test]
+            fn reverse_u64() {
+                assert_eq!(0x0123456789ABCDEFu64.swap_bits(), 0xF7B3D591E6A2C480u64);
+            }
+
+            #[cfg(feature = "u128")]
+            #[test]
+            fn reverse_u128() {
+                assert_eq!(
+                    0x0123456789ABCDEF0123456789ABCDEFu128.swap_bits(),
+                    0xF7B3D591E6A2C480F7B3D591E6A2C480u128
+                );
+            }
+
+            #[test]
+            fn reverse_usize() {
+                assert_eq!(0xFFusize.swap_bits(), 0xFFusize.swap_bytes());
+            }
+
+            #[test]
+            fn reverse_i8() {
+                assert_eq!(0xABi8.swap_bits(), 0xD5i8);
+            }
+
+            #[test]
+            fn reverse_i16() {
+                assert_eq!(0xABCDi16.swap_bits(), 0xB3D5i16);
+            }
+
+            #[test]
+            fn reverse_i32() {
+                assert_eq!(0xABCD2345i32.swap_bits(), 0xA2C4B3D5i32);
+            }
+
+            #[test]
+            fn reverse_i64() {
+                assert_eq!(0x0123456789ABCDEFi64.swap_bits(), 0xF7B3D591E6A2C480i64);
+            }
+
+            #[cfg(feature = "u128")]
+            #[test]
+            fn reverse_i128() {
+                assert_eq!(
+                    0x0123456789ABCDEF0123456789ABCDEFi128.swap_bits(),
+                    0xF7B3D591E6A2C480F7B3D591E6A2C480i128
+                );
+            }
+
+            #[test]
+            fn reverse_isize() {
+                assert_eq!(0xFFisize.swap_bits(), 0xFFisize.swap_bytes());
+            }
+
+            #[test]
+            fn reverse_low_bits_u8() {
+                assert_eq!(0b1011u8.swap_low_bits(4), 0b1101u8);
+                assert_eq!(0xABu8.swap_low_bits(8), 0xD5u8);
+                assert_eq!(0xABu8.swap_low_bits(0), 0u8);
+            }
+
+            #[test]
+            fn reverse_low_bits_u32() {
+                assert_eq!(0x04C11DB7u32.swap_low_bits(32), 0xEDB88320u32);
+                assert_eq!(0x1021u32.swap_low_bits(16), 0x8408u32);
+                assert_eq!(0xFFFF1021u32.swap_low_bits(16), 0x8408u32);
+            }
+
+            #[test]
+            fn reverse_low_bits_u64() {
+                assert_eq!(
+                    0x0123456789ABCDEFu64.swap_low_bits(60),
+                    0xF7B3D591E6A2C48u64
+                );
+            }
+
+            #[test]
+            fn reverse_low_bits_i16() {
+                assert_eq!((-1i16).swap_low_bits(3), 0b111i16);
+            }
+
+            #[test]
+            #[should_panic]
+            fn reverse_low_bits_too_wide() {
+                0u16.swap_low_bits(17);
+            }
         }
     };
 }
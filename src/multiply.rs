@@ -0,0 +1,115 @@
+//! Byte reversal with multiplications, from Bit Twiddling Hacks.
+//!
+//! Each byte is spread into several copies by a multiplication, the bits that end up in reversed
+//! positions are masked out, and a second multiplication or a modulus gathers them back into one
+//! byte. Wider types reverse each byte and then swap the byte order.
+//!
+//! ```
+//! use bit_reverse::multiply;
+//!
+//! assert_eq!(multiply::reverse_byte_3_ops(0xA0), 0x05);
+//! assert_eq!(multiply::reverse_byte_4_ops(0xA0), 0x05);
+//! assert_eq!(multiply::reverse_byte_7_ops(0xA0), 0x05);
+//! ```
+
+/// Reverses a byte with one 64 bit multiplication, an AND and a modulus. The modulus is slow on
+/// most processors, so this is mostly of interest where division is fast.
+#[inline]
+pub fn reverse_byte_3_ops(b: u8) -> u8 {
+    (((b as u64).wrapping_mul(0x0202020202) & 0x010884422010) % 1023) as u8
+}
+
+/// Reverses a byte with two 64 bit multiplications, an AND and a shift.
+#[inline]
+pub fn reverse_byte_4_ops(b: u8) -> u8 {
+    ((((b as u64).wrapping_mul(0x80200802) & 0x0884422110).wrapping_mul(0x0101010101)) >> 32) as u8
+}
+
+/// Reverses a byte with only 32 bit multiplications, for processors without a fast 64 bit
+/// multiplier.
+#[inline]
+pub fn reverse_byte_7_ops(b: u8) -> u8 {
+    let b = b as u32;
+    ((((b.wrapping_mul(0x0802) & 0x22110) | (b.wrapping_mul(0x8020) & 0x88440))
+        .wrapping_mul(0x10101))
+        >> 16) as u8
+}
+
+// The fastest variant for the target's word size.
+#[cfg(target_pointer_width = "64")]
+#[inline]
+fn reverse_byte(b: u8) -> u8 {
+    reverse_byte_4_ops(b)
+}
+
+#[cfg(not(target_pointer_width = "64"))]
+#[inline]
+fn reverse_byte(b: u8) -> u8 {
+    reverse_byte_7_ops(b)
+}
+
+/// Computes bit reversal by reversing each byte with multiplications, then swapping the byte
+/// order. On 64 bit targets each byte takes 4 operations, and 7 operations with 32 bit
+/// multiplications elsewhere.
+///
+/// # Timing
+/// Branch-free and table-free as written, on processors whose multiplication runs in constant
+/// time. Use `ConstTimeReverse` for secret values.
+pub trait MultiplyReverse {
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
+    fn swap_bits(self) -> Self;
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    fn swap_low_bits(self, bits: u32) -> Self;
+}
+
+macro_rules! doit_multiply { ($($ty:ident),*) => ($(
+    impl MultiplyReverse for $ty {
+        #[inline]
+        fn swap_bits(self) -> $ty {
+            // Reading the reversed bytes back in the opposite byte order swaps them.
+            let mut bytes = self.to_le_bytes();
+            for b in bytes.iter_mut() {
+                *b = reverse_byte(*b);
+            }
+            $ty::from_be_bytes(bytes)
+        }
+
+        swap_low_bits!(MultiplyReverse, $ty);
+    })*)
+}
+
+doit_multiply!(u8, u16, u32, u64, usize);
+#[cfg(feature = "u128")]
+doit_multiply!(u128);
+doit_signed!(MultiplyReverse);
+test_suite!();
+
+#[test]
+fn every_byte_variant() {
+    for b in 0..=255u8 {
+        let expected = ::LookupReverse::swap_bits(b);
+        assert_eq!(reverse_byte_3_ops(b), expected);
+        assert_eq!(reverse_byte_4_ops(b), expected);
+        assert_eq!(reverse_byte_7_ops(b), expected);
+    }
+}
+
+#[test]
+fn matches_other_backends() {
+    use {LookupReverse, ParallelReverse};
+
+    for x in ::xorshift().take(1000) {
+        let expected = ParallelReverse::swap_bits(x);
+        assert_eq!(MultiplyReverse::swap_bits(x), expected);
+        assert_eq!(LookupReverse::swap_bits(x), expected);
+        assert_eq!(
+            MultiplyReverse::swap_bits(x as u32),
+            ParallelReverse::swap_bits(x as u32)
+        );
+    }
+}
@@ -0,0 +1,76 @@
+/// Computes bit reversal with the standard library's `reverse_bits`, which the compiler lowers to
+/// a single instruction where the target has one, such as `rbit` on ARM, and to an optimized
+/// sequence elsewhere.
+///
+/// # Timing
+/// Depends on how the compiler lowers `reverse_bits` for the target, which isn't guaranteed to be
+/// constant time. Use `ConstTimeReverse` for secret values.
+pub trait NativeReverse {
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
+    fn swap_bits(self) -> Self;
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    fn swap_low_bits(self, bits: u32) -> Self;
+}
+
+macro_rules! doit_native { ($($ty:ty),*) => ($(
+    impl NativeReverse for $ty {
+        #[inline]
+        fn swap_bits(self) -> $ty {
+            self.reverse_bits()
+        }
+
+        swap_low_bits!(NativeReverse, $ty);
+    })*)
+}
+
+doit_native!(u8, u16, u32, u64, usize);
+#[cfg(feature = "u128")]
+doit_native!(u128);
+doit_signed!(NativeReverse);
+test_suite!();
+
+// Checks every other algorithm against the compiler's lowering on an xorshift sequence.
+#[cfg(test)]
+macro_rules! differential_test { ($($name:ident: $ty:ty),*) => ($(
+    #[test]
+    fn $name() {
+        use {BitwiseReverse, LookupReverse, ParallelReverse};
+
+        let width = 8 * ::core::mem::size_of::<$ty>() as u32;
+        for (i, x) in (0..1000u32).zip(::xorshift()) {
+            // Rotating fills the high half of u128 as well.
+            let v = (x as $ty) ^ (x as $ty).rotate_left(width / 2);
+            let expected = NativeReverse::swap_bits(v);
+            assert_eq!(BitwiseReverse::swap_bits(v), expected);
+            assert_eq!(LookupReverse::swap_bits(v), expected);
+            assert_eq!(ParallelReverse::swap_bits(v), expected);
+
+            let bits = i % (width + 1);
+            let expected = NativeReverse::swap_low_bits(v, bits);
+            assert_eq!(BitwiseReverse::swap_low_bits(v, bits), expected);
+            assert_eq!(LookupReverse::swap_low_bits(v, bits), expected);
+            assert_eq!(ParallelReverse::swap_low_bits(v, bits), expected);
+        }
+    })*)
+}
+
+#[cfg(test)]
+differential_test!(
+    matches_u8: u8,
+    matches_u16: u16,
+    matches_u32: u32,
+    matches_u64: u64,
+    matches_usize: usize,
+    matches_i8: i8,
+    matches_i16: i16,
+    matches_i32: i32,
+    matches_i64: i64,
+    matches_isize: isize
+);
+#[cfg(all(test, feature = "u128"))]
+differential_test!(matches_u128: u128, matches_i128: i128);
@@ -0,0 +1,39 @@
+/// Computes bit reversal one nibble at a time, using a 16 entry lookup table packed into 8 bytes.
+/// This is for targets where the 256 byte table of `LookupReverse` is too big.
+///
+/// # Timing
+/// Not constant time. The table is indexed by the nibbles of the value, so the running time can
+/// leak the value through the cache.
+pub trait NibbleLookupReverse {
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
+    fn swap_bits(self) -> Self;
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    fn swap_low_bits(self, bits: u32) -> Self;
+}
+
+// Byte i holds the reverse of nibble 2i in its low nibble and the reverse of nibble 2i+1 in its
+// high nibble.
+const NIBBLE_LOOKUP: [u8; 8] = [0x80, 0xC4, 0xA2, 0xE6, 0x91, 0xD5, 0xB3, 0xF7];
+
+#[inline]
+fn reverse_nibble(n: u8) -> u8 {
+    (NIBBLE_LOOKUP[(n >> 1) as usize] >> ((n & 1) * 4)) & 0xF
+}
+
+doit_nibble!(NibbleLookupReverse, reverse_nibble; u8, u16, u32, u64, usize);
+#[cfg(feature = "u128")]
+doit_nibble!(NibbleLookupReverse, reverse_nibble; u128);
+doit_signed!(NibbleLookupReverse);
+test_suite!();
+
+#[test]
+fn packed_table() {
+    for n in 0..16u8 {
+        assert_eq!(reverse_nibble(n), n.reverse_bits() >> 4);
+    }
+}
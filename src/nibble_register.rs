@@ -0,0 +1,38 @@
+/// Computes bit reversal one nibble at a time like `NibbleLookupReverse`, but with the 16 entry
+/// table held in a `u64` constant, so it needs no memory at all.
+///
+/// # Timing
+/// Not constant time on targets without a constant time variable shift, since the table is read
+/// by shifting it by the value of each nibble.
+pub trait NibbleRegisterReverse {
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
+    fn swap_bits(self) -> Self;
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    fn swap_low_bits(self, bits: u32) -> Self;
+}
+
+// Nibble n holds the reverse of n.
+const NIBBLE_REGISTER: u64 = 0xF7B3D591E6A2C480;
+
+#[inline]
+fn reverse_nibble(n: u8) -> u8 {
+    (NIBBLE_REGISTER >> (n * 4)) as u8 & 0xF
+}
+
+doit_nibble!(NibbleRegisterReverse, reverse_nibble; u8, u16, u32, u64, usize);
+#[cfg(feature = "u128")]
+doit_nibble!(NibbleRegisterReverse, reverse_nibble; u128);
+doit_signed!(NibbleRegisterReverse);
+test_suite!();
+
+#[test]
+fn register_table() {
+    for n in 0..16u8 {
+        assert_eq!(reverse_nibble(n), n.reverse_bits() >> 4);
+    }
+}
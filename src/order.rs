@@ -0,0 +1,38 @@
+/// The order in which the bits of a byte or word are sent, received or packed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BitOrder {
+    /// The least significant bit comes first.
+    LsbFirst,
+    /// The most significant bit comes first.
+    MsbFirst,
+}
+
+/// The order in which the bytes of a multi-byte value are stored.
+pub trait Endian {
+    /// Whether the most significant byte is stored first.
+    const IS_BIG_ENDIAN: bool;
+}
+
+/// Stores the most significant byte first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BigEndian {}
+
+/// Stores the least significant byte first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LittleEndian {}
+
+impl Endian for BigEndian {
+    const IS_BIG_ENDIAN: bool = true;
+}
+
+impl Endian for LittleEndian {
+    const IS_BIG_ENDIAN: bool = false;
+}
+
+/// The byte order of the target.
+#[cfg(target_endian = "big")]
+pub type NativeEndian = BigEndian;
+
+/// The byte order of the target.
+#[cfg(target_endian = "little")]
+pub type NativeEndian = LittleEndian;
@@ -1,30 +1,65 @@
+//! The parallel algorithm, along with `const fn` versions of it for every primitive integer.
+//!
+//! ```
+//! use bit_reverse::parallel::reverse_u16;
+//!
+//! const REFLECTED_POLY: u16 = reverse_u16(0x1021);
+//! assert_eq!(REFLECTED_POLY, 0x8408);
+//! ```
+
 /// Computes bit reversal by using a divide and conquer approach. Pairs of bits are swapped.
 /// Then neighboring bit pairs are swapped. Each time swapping the next largest group of bits.
 /// This is done until the entire data has been bit reversed.
+///
+/// # Timing
+/// Branch-free and table-free as written, but nothing stops the compiler from transforming it.
+/// Use `ConstTimeReverse` for secret values.
 pub trait ParallelReverse {
     /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
     fn swap_bits(self) -> Self;
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    fn swap_low_bits(self, bits: u32) -> Self;
 }
 
-macro_rules! doit_parallel { ($($ty:ty),*) => ($(
+macro_rules! doit_parallel { ($($name:ident: $ty:ty),*) => ($(
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the type.
+    #[inline]
+    pub const fn $name(mut v: $ty) -> $ty {
+        // Swap odd and even bits
+        v = ((v >> 1) & (0x55555555555555555555555555555555 as $ty)) | ((v & (0x55555555555555555555555555555555 as $ty)) << 1);
+        // Swap consecutive pairs
+        v = ((v >> 2) & (0x33333333333333333333333333333333 as $ty)) | ((v & (0x33333333333333333333333333333333 as $ty)) << 2);
+        // Swap nibbles
+        v = ((v >> 4) & (0x0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F as $ty)) | ((v & (0x0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F as $ty)) << 4);
+
+        v.swap_bytes()
+    }
+
     impl ParallelReverse for $ty {
         #[inline]
         fn swap_bits(self) -> $ty {
-            let mut v = self;
-            // Swap odd and even bits
-            v = ((v >> 1) & (0x55555555555555555555555555555555 as $ty)) | ((v & (0x55555555555555555555555555555555 as $ty)) << 1);
-            // Swap consecutive pairs
-            v = ((v >> 2) & (0x33333333333333333333333333333333 as $ty)) | ((v & (0x33333333333333333333333333333333 as $ty)) << 2);
-            // Swap nibbles
-            v = ((v >> 4) & (0x0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F as $ty)) | ((v & (0x0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F as $ty)) << 4);
-
-            v.swap_bytes()
+            $name(self)
         }
+
+        swap_low_bits!(ParallelReverse, $ty);
     })*)
 }
 
-doit_parallel!(u8, u16, u32, u64, usize);
+doit_parallel!(
+    reverse_u8: u8,
+    reverse_u16: u16,
+    reverse_u32: u32,
+    reverse_u64: u64,
+    reverse_usize: usize
+);
 #[cfg(feature = "u128")]
-doit_parallel!(u128);
+doit_parallel!(reverse_u128: u128);
+const_signed!();
 doit_signed!(ParallelReverse);
 test_suite!();
+const_test_suite!();
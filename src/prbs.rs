@@ -0,0 +1,394 @@
+//! Pseudo-random binary sequence generators and checkers following ITU-T O.150.
+//!
+//! ```
+//! use bit_reverse::prbs::{Checker, Generator, Pattern};
+//! use bit_reverse::BitOrder;
+//!
+//! let mut buf = [0u8; 64];
+//! Generator::new(Pattern::PRBS31).fill(&mut buf, BitOrder::LsbFirst);
+//!
+//! let mut checker = Checker::new(Pattern::PRBS31);
+//! checker.check(&buf, BitOrder::LsbFirst);
+//! assert!(checker.is_synced());
+//! assert_eq!(checker.errors(), 0);
+//! ```
+
+use swap_bits_slice;
+use BitOrder;
+use ParallelReverse;
+
+/// A PRBS pattern defined by the feedback polynomial x^degree + x^tap + 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    degree: u32,
+    tap: u32,
+    inverted: bool,
+}
+
+impl Pattern {
+    /// PRBS7, x^7 + x^6 + 1.
+    pub const PRBS7: Pattern = Pattern::new(7, 6);
+
+    /// PRBS9, x^9 + x^5 + 1.
+    pub const PRBS9: Pattern = Pattern::new(9, 5);
+
+    /// PRBS15, x^15 + x^14 + 1, sent inverted.
+    pub const PRBS15: Pattern = Pattern::new(15, 14).invert();
+
+    /// PRBS23, x^23 + x^18 + 1, sent inverted.
+    pub const PRBS23: Pattern = Pattern::new(23, 18).invert();
+
+    /// PRBS31, x^31 + x^28 + 1, sent inverted.
+    pub const PRBS31: Pattern = Pattern::new(31, 28).invert();
+
+    /// Creates the pattern for the polynomial x^`degree` + x^`tap` + 1, sent as is.
+    ///
+    /// # Panics
+    /// Panics unless `1 <= tap < degree <= 32`.
+    pub const fn new(degree: u32, tap: u32) -> Pattern {
+        assert!(
+            1 <= tap && tap < degree && degree <= 32,
+            "a PRBS pattern needs 1 <= tap < degree <= 32"
+        );
+        Pattern {
+            degree,
+            tap,
+            inverted: false,
+        }
+    }
+
+    /// Returns the same pattern with the register output inverted before it is sent.
+    pub const fn invert(self) -> Pattern {
+        Pattern {
+            inverted: !self.inverted,
+            ..self
+        }
+    }
+
+    /// The length of the shift register, which generates a sequence of 2^degree - 1 bits.
+    pub const fn degree(&self) -> u32 {
+        self.degree
+    }
+
+    /// The second tap of the feedback polynomial.
+    pub const fn tap(&self) -> u32 {
+        self.tap
+    }
+
+    /// Whether the register output is inverted before it is sent.
+    pub const fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+
+    fn mask(&self) -> u32 {
+        !0 >> (32 - self.degree)
+    }
+
+    // The register shifts left and the new bit enters at the bottom, so the oldest bit lives at
+    // bit degree-1.
+    #[inline]
+    fn feedback(&self, state: u32) -> bool {
+        ((state >> (self.degree - 1)) ^ (state >> (self.tap - 1))) & 1 != 0
+    }
+
+    #[inline]
+    fn shift(&self, state: u32, bit: bool) -> u32 {
+        ((state << 1) | bit as u32) & self.mask()
+    }
+}
+
+/// Unsigned integers that PRBS bits can be packed into.
+pub trait Word: ParallelReverse + Copy {
+    /// Shifts `bit` into the least significant end of the word.
+    fn push_bit(self, bit: bool) -> Self;
+
+    /// Returns bit i of the word counting from the most significant end.
+    fn msb_bit(self, i: u32) -> bool;
+
+    /// The length of the word in bits.
+    fn bits() -> u32;
+}
+
+macro_rules! doit_word { ($($ty:ty),*) => ($(
+    impl Word for $ty {
+        #[inline]
+        fn push_bit(self, bit: bool) -> $ty {
+            (self << 1) | bit as $ty
+        }
+
+        #[inline]
+        fn msb_bit(self, i: u32) -> bool {
+            (self >> (Self::bits() - 1 - i)) & 1 != 0
+        }
+
+        #[inline]
+        fn bits() -> u32 {
+            8 * ::core::mem::size_of::<$ty>() as u32
+        }
+    })*)
+}
+
+doit_word!(u8, u16, u32, u64);
+#[cfg(feature = "u128")]
+doit_word!(u128);
+
+/// Generates a PRBS pattern.
+#[derive(Clone, Debug)]
+pub struct Generator {
+    pattern: Pattern,
+    state: u32,
+}
+
+impl Generator {
+    /// Starts generating `pattern` from the all ones register state.
+    pub fn new(pattern: Pattern) -> Generator {
+        Generator::with_seed(pattern, !0)
+    }
+
+    /// Starts generating `pattern` from the lowest `degree` bits of `seed`.
+    ///
+    /// # Panics
+    /// Panics if the lowest `degree` bits of `seed` are all zero, which would lock up the register.
+    pub fn with_seed(pattern: Pattern, seed: u32) -> Generator {
+        let state = seed & pattern.mask();
+        assert!(state != 0, "a PRBS register cannot be seeded with zero");
+        Generator { pattern, state }
+    }
+
+    /// Returns the next bit of the pattern.
+    #[inline]
+    pub fn next_bit(&mut self) -> bool {
+        let bit = self.pattern.feedback(self.state);
+        self.state = self.pattern.shift(self.state, bit);
+        bit ^ self.pattern.inverted
+    }
+
+    /// Fills `buf` with the next bits of the pattern, packing each word in `order`.
+    pub fn fill<T: Word>(&mut self, buf: &mut [T], order: BitOrder) {
+        for word in buf.iter_mut() {
+            for _ in 0..T::bits() {
+                *word = word.push_bit(self.next_bit());
+            }
+        }
+        if order == BitOrder::LsbFirst {
+            swap_bits_slice(buf);
+        }
+    }
+}
+
+// A synchronized checker drops sync once more than this many of the last 64 bits were errors.
+const LOSS_OF_SYNC_ERRORS: u32 = 16;
+
+/// Checks received data against a PRBS pattern.
+///
+/// The checker synchronizes itself by loading received bits into its register until it has
+/// predicted twice `degree` bits in a row correctly. After that it runs freely and counts every
+/// received bit that differs from the pattern. If errors become too dense it loses sync and starts
+/// over.
+#[derive(Clone, Debug)]
+pub struct Checker {
+    pattern: Pattern,
+    state: u32,
+    loaded: u32,
+    run: u32,
+    synced: bool,
+    history: u64,
+    errors: u64,
+    bits: u64,
+}
+
+impl Checker {
+    /// Creates an unsynchronized checker for `pattern`.
+    pub fn new(pattern: Pattern) -> Checker {
+        Checker {
+            pattern,
+            state: 0,
+            loaded: 0,
+            run: 0,
+            synced: false,
+            history: 0,
+            errors: 0,
+            bits: 0,
+        }
+    }
+
+    /// Whether the checker is synchronized to the received pattern.
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// The number of bit errors seen while synchronized.
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// The number of bits checked while synchronized.
+    pub fn bits_checked(&self) -> u64 {
+        self.bits
+    }
+
+    /// Checks the next received bit.
+    pub fn check_bit(&mut self, received: bool) {
+        let bit = received ^ self.pattern.inverted;
+        let expected = self.pattern.feedback(self.state);
+
+        if self.synced {
+            let error = bit != expected;
+            self.state = self.pattern.shift(self.state, expected);
+            self.history = (self.history << 1) | error as u64;
+            self.bits += 1;
+            if error {
+                self.errors += 1;
+                if self.history.count_ones() > LOSS_OF_SYNC_ERRORS {
+                    self.synced = false;
+                    self.loaded = 0;
+                    self.run = 0;
+                }
+            }
+            return;
+        }
+
+        // Until the register holds degree received bits its predictions are meaningless.
+        if self.loaded < self.pattern.degree {
+            self.loaded += 1;
+        } else if bit == expected {
+            self.run += 1;
+        } else {
+            self.run = 0;
+        }
+        self.state = self.pattern.shift(self.state, bit);
+
+        if self.run >= 2 * self.pattern.degree {
+            self.synced = true;
+            self.history = 0;
+        }
+    }
+
+    /// Checks received words whose bits were packed in `order`.
+    pub fn check<T: Word>(&mut self, data: &[T], order: BitOrder) {
+        for &word in data {
+            let word = match order {
+                BitOrder::LsbFirst => word.swap_bits(),
+                BitOrder::MsbFirst => word,
+            };
+            for i in 0..T::bits() {
+                self.check_bit(word.msb_bit(i));
+            }
+        }
+    }
+}
+
+#[test]
+fn maximal_length_periods() {
+    for pattern in [Pattern::PRBS7, Pattern::PRBS9, Pattern::PRBS15].iter() {
+        let mut generator = Generator::new(*pattern);
+        let start = generator.state;
+        let mut period = 0u32;
+        loop {
+            generator.next_bit();
+            period += 1;
+            if generator.state == start {
+                break;
+            }
+        }
+        assert_eq!(period, (1 << pattern.degree) - 1);
+    }
+}
+
+#[test]
+fn prbs7_msb_first_bytes() {
+    let mut buf = [0u8; 4];
+    Generator::new(Pattern::PRBS7).fill(&mut buf, BitOrder::MsbFirst);
+    // The all ones register first shifts out six zeros and then the first one.
+    assert_eq!(buf[0] >> 1, 0b0000001);
+}
+
+#[test]
+fn bit_orders_are_reversed_bytes() {
+    let mut msb = [0u8; 32];
+    let mut lsb = [0u8; 32];
+    Generator::new(Pattern::PRBS23).fill(&mut msb, BitOrder::MsbFirst);
+    Generator::new(Pattern::PRBS23).fill(&mut lsb, BitOrder::LsbFirst);
+    for (m, l) in msb.iter().zip(lsb.iter()) {
+        assert_eq!(m.swap_bits(), *l);
+    }
+}
+
+#[test]
+fn words_match_bytes() {
+    let mut bytes = [0u8; 16];
+    let mut words = [0u32; 4];
+    Generator::new(Pattern::PRBS31).fill(&mut bytes, BitOrder::MsbFirst);
+    Generator::new(Pattern::PRBS31).fill(&mut words, BitOrder::MsbFirst);
+    for (chunk, word) in bytes.chunks(4).zip(words.iter()) {
+        assert_eq!(chunk, &word.to_be_bytes()[..]);
+    }
+}
+
+#[test]
+fn checker_syncs_and_counts_errors() {
+    let mut buf = [0u16; 64];
+    Generator::with_seed(Pattern::PRBS15, 0x1234).fill(&mut buf, BitOrder::LsbFirst);
+
+    let mut checker = Checker::new(Pattern::PRBS15);
+    checker.check(&buf[..8], BitOrder::LsbFirst);
+    assert!(checker.is_synced());
+    assert_eq!(checker.errors(), 0);
+
+    buf[20] ^= 0x0101;
+    buf[40] ^= 0x8000;
+    checker.check(&buf[8..], BitOrder::LsbFirst);
+    assert!(checker.is_synced());
+    assert_eq!(checker.errors(), 3);
+}
+
+#[test]
+fn checker_rejects_wrong_order() {
+    let mut buf = [0u8; 64];
+    Generator::new(Pattern::PRBS9).fill(&mut buf, BitOrder::MsbFirst);
+
+    let mut checker = Checker::new(Pattern::PRBS9);
+    checker.check(&buf, BitOrder::LsbFirst);
+    assert!(!checker.is_synced());
+}
+
+#[test]
+fn checker_loses_sync_on_wrong_pattern() {
+    let mut buf = [0u8; 64];
+    Generator::new(Pattern::PRBS7).fill(&mut buf, BitOrder::MsbFirst);
+    let mut noise = [0u8; 64];
+    Generator::new(Pattern::PRBS31).fill(&mut noise, BitOrder::MsbFirst);
+
+    let mut checker = Checker::new(Pattern::PRBS7);
+    checker.check(&buf, BitOrder::MsbFirst);
+    assert!(checker.is_synced());
+    checker.check(&noise[..16], BitOrder::MsbFirst);
+    assert!(!checker.is_synced());
+}
+
+#[test]
+fn custom_patterns() {
+    assert_eq!(Pattern::new(31, 28).invert(), Pattern::PRBS31);
+    assert!(!Pattern::PRBS9.is_inverted());
+    assert_eq!((Pattern::PRBS23.degree(), Pattern::PRBS23.tap()), (23, 18));
+
+    // PRBS11 from O.150, x^11 + x^9 + 1.
+    let mut buf = [0u8; 64];
+    Generator::new(Pattern::new(11, 9)).fill(&mut buf, BitOrder::MsbFirst);
+    let mut checker = Checker::new(Pattern::new(11, 9));
+    checker.check(&buf, BitOrder::MsbFirst);
+    assert!(checker.is_synced());
+    assert_eq!(checker.errors(), 0);
+}
+
+#[test]
+#[should_panic]
+fn pattern_without_tap() {
+    Pattern::new(7, 0);
+}
+
+#[test]
+#[should_panic]
+fn pattern_too_long() {
+    Pattern::new(33, 28);
+}
@@ -0,0 +1,176 @@
+//! Bit reversal for code that is generic over `num_traits::PrimInt`.
+//!
+//! The parallel algorithm's masks are generated from the width of the type instead of being
+//! written out for each one, so `PrimIntReverse` is implemented for every `T: PrimInt` and generic
+//! code needs no other bounds.
+//!
+//! ```
+//! extern crate bit_reverse;
+//! extern crate num_traits;
+//!
+//! use bit_reverse::PrimIntReverse;
+//! use num_traits::PrimInt;
+//!
+//! fn reflect<T: PrimInt>(poly: T) -> T {
+//!     poly.swap_bits()
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(reflect(0x04C11DB7u32), 0xEDB88320);
+//! assert_eq!(reflect(0x1021u16), 0x8408);
+//! # }
+//! ```
+
+use num_traits::PrimInt;
+
+/// Computes bit reversal for any `PrimInt` with the parallel algorithm, using masks generated for
+/// the width of the type.
+///
+/// # Timing
+/// Branch-free and table-free as written, but nothing stops the compiler from transforming it.
+/// Use `ConstTimeReverse` for secret values.
+pub trait PrimIntReverse: PrimInt {
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
+    fn swap_bits(self) -> Self;
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    fn swap_low_bits(self, bits: u32) -> Self;
+
+    /// Generalized reverse, see `grev`.
+    fn grev(self, k: u32) -> Self;
+}
+
+impl<T: PrimInt> PrimIntReverse for T {
+    #[inline]
+    fn swap_bits(self) -> T {
+        swap_bits(self)
+    }
+
+    #[inline]
+    fn swap_low_bits(self, bits: u32) -> T {
+        swap_low_bits(self, bits)
+    }
+
+    #[inline]
+    fn grev(self, k: u32) -> T {
+        grev(self, k)
+    }
+}
+
+/// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
+#[inline]
+pub fn swap_bits<T: PrimInt>(value: T) -> T {
+    // Reversing the bits within each byte is a generalized reverse by 7, and swapping the bytes
+    // finishes the job, just like the fixed width parallel algorithm.
+    grev(value, 7).swap_bytes()
+}
+
+/// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+/// are cleared.
+///
+/// # Panics
+/// Panics if `bits` is larger than the length of the T in bits.
+#[inline]
+pub fn swap_low_bits<T: PrimInt>(value: T, bits: u32) -> T {
+    let width = T::zero().count_zeros();
+    assert!(
+        bits <= width,
+        "cannot reverse more bits than the type holds"
+    );
+
+    // Reverse the whole value and shift the reversed low bits back down. The shift would
+    // overflow when no bits are reversed, so that case is handled separately.
+    if bits == 0 {
+        T::zero()
+    } else {
+        swap_bits(value).unsigned_shr(width - bits)
+    }
+}
+
+/// Generalized reverse. Bit i moves to bit i XOR `k`, so each set bit of `k` swaps neighbouring
+/// groups of that size. `k` of N-1 reverses all the bits, 7 reverses the bits of each byte, and
+/// N-8 swaps the bytes. Bits of `k` at or above the width of the T are ignored.
+#[inline]
+pub fn grev<T: PrimInt>(value: T, k: u32) -> T {
+    let width = T::zero().count_zeros();
+    let mut v = value;
+
+    // The mask for groups of `shift` bits selects the low group of every pair. The mask for the
+    // widest groups is the low half, and each narrower mask follows from the one before it.
+    let mut shift = width / 2;
+    let mut mask = (!T::zero()).unsigned_shr(shift);
+    while shift > 0 {
+        if k & shift != 0 {
+            v = (v & mask).unsigned_shl(shift) | (v.unsigned_shr(shift) & mask);
+        }
+        shift /= 2;
+        mask = mask ^ mask.unsigned_shl(shift);
+    }
+    v
+}
+
+#[test]
+fn matches_parallel() {
+    use ParallelReverse;
+
+    for x in ::xorshift().take(1000) {
+        assert_eq!(swap_bits(x as u8), ParallelReverse::swap_bits(x as u8));
+        assert_eq!(swap_bits(x as u16), ParallelReverse::swap_bits(x as u16));
+        assert_eq!(swap_bits(x as u32), ParallelReverse::swap_bits(x as u32));
+        assert_eq!(swap_bits(x), ParallelReverse::swap_bits(x));
+        assert_eq!(
+            swap_bits(x as usize),
+            ParallelReverse::swap_bits(x as usize)
+        );
+        assert_eq!(swap_bits(x as i8), ParallelReverse::swap_bits(x as i8));
+        assert_eq!(swap_bits(x as i32), ParallelReverse::swap_bits(x as i32));
+        assert_eq!(swap_bits(x as i64), ParallelReverse::swap_bits(x as i64));
+        assert_eq!(
+            swap_low_bits(x as u32, 12),
+            ParallelReverse::swap_low_bits(x as u32, 12)
+        );
+        assert_eq!(
+            swap_low_bits(x as i16, 3),
+            ParallelReverse::swap_low_bits(x as i16, 3)
+        );
+    }
+}
+
+#[cfg(feature = "u128")]
+#[test]
+fn matches_parallel_u128() {
+    use ParallelReverse;
+
+    let x = 0x0123456789ABCDEF0123456789ABCDEFu128;
+    assert_eq!(swap_bits(x), ParallelReverse::swap_bits(x));
+    assert_eq!(swap_bits(x as i128), ParallelReverse::swap_bits(x as i128));
+}
+
+#[test]
+fn generalized_reverse() {
+    let x = 0x0123456789ABCDEFu64;
+    assert_eq!(grev(x, 0), x);
+    assert_eq!(grev(x, 63), swap_bits(x));
+    assert_eq!(grev(x, 56), x.swap_bytes());
+    assert_eq!(grev(0x12345678u32, 16), 0x56781234);
+    assert_eq!(grev(0x12345678u32, 4), 0x21436587);
+    assert_eq!(grev(0xF0u8, 64 + 4), 0x0F);
+    assert_eq!(grev(-2i8, 1), -3i8);
+}
+
+#[test]
+fn low_bits() {
+    assert_eq!(0b1011u8.swap_low_bits(4), 0b1101u8);
+    assert_eq!(0xABu8.swap_low_bits(0), 0u8);
+    assert_eq!(0xFFFF1021u32.swap_low_bits(16), 0x8408u32);
+}
+
+#[test]
+#[should_panic]
+fn low_bits_too_wide() {
+    0u16.swap_low_bits(17);
+}
@@ -0,0 +1,330 @@
+//! Bit numbering conversions and signal extraction for byte arrays laid out like CAN messages.
+//!
+//! LSB0 numbering counts bits from the least significant bit, while MSB0 numbering, used by
+//! PowerPC datasheets among others, counts from the most significant bit. For a whole register,
+//! converting a bit mask from one numbering to the other is a bit reversal.
+//!
+//! Signals are placed the way DBC files describe them. Intel signals are little-endian: the start
+//! bit is the signal's least significant bit, and bits are numbered LSB0 through the bytes in
+//! order. Motorola signals are big-endian: the start bit is the signal's most significant bit,
+//! given in "sawtooth" numbering where bit 7 of byte 0 is followed by bit 6 down to bit 0, and
+//! then by bit 7 of byte 1.
+//!
+//! ```
+//! use bit_reverse::signal::{Layout, Signal};
+//!
+//! // DBC: SG_ Speed : 3|12@0+ (Motorola)
+//! let speed = Signal::new(3, 12, Layout::Motorola);
+//! assert_eq!(speed.extract(&[0x0A, 0xBC]), Some(0xABC));
+//! ```
+
+use bitio::{BitReader, BitWriter, BufferFull};
+use BitOrder;
+
+/// Converts an LSB0 bit index into MSB0 numbering for a `width` bit value.
+///
+/// # Panics
+/// Panics if `bit` isn't less than `width`.
+#[inline]
+pub fn lsb0_to_msb0(bit: u32, width: u32) -> u32 {
+    assert!(bit < width, "bit index out of range");
+    width - 1 - bit
+}
+
+/// Converts an MSB0 bit index into LSB0 numbering for a `width` bit value.
+///
+/// # Panics
+/// Panics if `bit` isn't less than `width`.
+#[inline]
+pub fn msb0_to_lsb0(bit: u32, width: u32) -> u32 {
+    lsb0_to_msb0(bit, width)
+}
+
+/// Converts a sawtooth bit index, as used for Motorola signals in DBC files, into the MSB0 index
+/// of the same bit when the bytes are read as one big-endian value.
+#[inline]
+pub fn sawtooth_to_msb0(bit: u32) -> u32 {
+    (bit / 8) * 8 + lsb0_to_msb0(bit % 8, 8)
+}
+
+/// Converts an MSB0 index into a big-endian byte array into sawtooth numbering.
+#[inline]
+pub fn msb0_to_sawtooth(bit: u32) -> u32 {
+    sawtooth_to_msb0(bit)
+}
+
+/// How a signal's bits are laid out across bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Layout {
+    /// Little-endian, marked `@1` in DBC files.
+    Intel,
+    /// Big-endian, marked `@0` in DBC files.
+    Motorola,
+}
+
+/// The position of a signal within a message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Signal {
+    start_bit: u32,
+    length: u32,
+    layout: Layout,
+}
+
+impl Signal {
+    /// Creates a signal from its DBC start bit, length and layout.
+    ///
+    /// # Panics
+    /// Panics if `length` isn't between 1 and 64.
+    pub fn new(start_bit: u32, length: u32, layout: Layout) -> Signal {
+        assert!(
+            (1..=64).contains(&length),
+            "signal length must be between 1 and 64 bits"
+        );
+        Signal {
+            start_bit,
+            length,
+            layout,
+        }
+    }
+
+    /// The start bit as written in a DBC file.
+    #[inline]
+    pub fn start_bit(&self) -> u32 {
+        self.start_bit
+    }
+
+    /// The length of the signal in bits.
+    #[inline]
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    /// The layout of the signal.
+    #[inline]
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    // Both layouts are a contiguous run of bits in one of the two bit stream orders: LSB-first
+    // from the start bit for Intel, or MSB-first from the start bit's MSB0 index for Motorola.
+    fn stream_position(&self) -> (usize, BitOrder) {
+        match self.layout {
+            Layout::Intel => (self.start_bit as usize, BitOrder::LsbFirst),
+            Layout::Motorola => (
+                sawtooth_to_msb0(self.start_bit) as usize,
+                BitOrder::MsbFirst,
+            ),
+        }
+    }
+
+    /// Extracts the raw unsigned value of the signal, or returns `None` if the signal doesn't fit
+    /// in `data`.
+    pub fn extract(&self, data: &[u8]) -> Option<u64> {
+        let (pos, order) = self.stream_position();
+        let mut reader = BitReader::new(data, order);
+        reader.skip_bits(pos)?;
+        reader.read_bits(self.length)
+    }
+
+    /// Extracts the raw value of the signal as a two's complement signed integer, or returns
+    /// `None` if the signal doesn't fit in `data`.
+    pub fn extract_signed(&self, data: &[u8]) -> Option<i64> {
+        let unused = 64 - self.length;
+        self.extract(data)
+            .map(|value| ((value << unused) as i64) >> unused)
+    }
+
+    /// Writes the low `length` bits of `value` into the signal, leaving every other bit of `data`
+    /// unchanged. Returns `BufferFull` without writing anything if the signal doesn't fit in
+    /// `data`.
+    pub fn insert(&self, data: &mut [u8], value: u64) -> Result<(), BufferFull> {
+        let (pos, order) = self.stream_position();
+        let mut writer = BitWriter::new(data, order);
+        writer.skip_bits(pos)?;
+        writer.write_bits(value, self.length)
+    }
+}
+
+#[cfg(test)]
+const TEST_DATA: [u8; 8] = [0x5A, 0xC3, 0x0F, 0x96, 0x21, 0xE8, 0x7B, 0x04];
+
+// Walks a signal bit by bit the way the DBC format defines it, from the value's most significant
+// bit to its least significant bit, returning the LSB0 byte array index of each bit.
+#[cfg(test)]
+fn reference_bit(signal: &Signal, i: u32) -> Option<u32> {
+    match signal.layout {
+        Layout::Intel => Some(signal.start_bit + signal.length - 1 - i),
+        Layout::Motorola => {
+            let mut pos = signal.start_bit;
+            for _ in 0..i {
+                pos = if pos.is_multiple_of(8) {
+                    pos + 15
+                } else {
+                    pos - 1
+                };
+            }
+            Some(pos)
+        }
+    }
+}
+
+#[cfg(test)]
+fn reference_extract(signal: &Signal, data: &[u8]) -> Option<u64> {
+    let mut value = 0;
+    for i in 0..signal.length {
+        let pos = reference_bit(signal, i)?;
+        let byte = data.get((pos / 8) as usize)?;
+        value = (value << 1) | ((byte >> (pos % 8)) & 1) as u64;
+    }
+    Some(value)
+}
+
+#[test]
+fn numbering_conversions() {
+    assert_eq!(lsb0_to_msb0(0, 32), 31);
+    assert_eq!(lsb0_to_msb0(31, 32), 0);
+    assert_eq!(msb0_to_lsb0(6, 16), 9);
+    assert_eq!(sawtooth_to_msb0(7), 0);
+    assert_eq!(sawtooth_to_msb0(0), 7);
+    assert_eq!(sawtooth_to_msb0(15), 8);
+    assert_eq!(msb0_to_sawtooth(sawtooth_to_msb0(42)), 42);
+}
+
+#[test]
+#[should_panic]
+fn numbering_out_of_range() {
+    lsb0_to_msb0(8, 8);
+}
+
+#[test]
+fn intel_examples() {
+    let data = [0x34, 0x12];
+    assert_eq!(
+        Signal::new(0, 16, Layout::Intel).extract(&data),
+        Some(0x1234)
+    );
+    let data = [0xA0, 0xBC];
+    assert_eq!(
+        Signal::new(4, 12, Layout::Intel).extract(&data),
+        Some(0xBCA)
+    );
+    assert_eq!(Signal::new(4, 13, Layout::Intel).extract(&data), None);
+}
+
+#[test]
+fn motorola_examples() {
+    let data = [0x12, 0x34];
+    assert_eq!(
+        Signal::new(7, 16, Layout::Motorola).extract(&data),
+        Some(0x1234)
+    );
+    let data = [0x0A, 0xBC];
+    assert_eq!(
+        Signal::new(3, 12, Layout::Motorola).extract(&data),
+        Some(0xABC)
+    );
+    // A signal starting at the bottom of byte 0 continues at the top of byte 1.
+    let data = [0x01, 0x23, 0x40];
+    assert_eq!(
+        Signal::new(0, 12, Layout::Motorola).extract(&data),
+        Some(0x91A)
+    );
+    assert_eq!(Signal::new(0, 18, Layout::Motorola).extract(&data), None);
+}
+
+#[test]
+fn dbc_signals() {
+    // J1939 EEC1: SG_ EngineSpeed : 24|16@1+ (0.125,0) [0|8031.875] "rpm"
+    let engine_speed = Signal::new(24, 16, Layout::Intel);
+    let frame = [0xFF, 0xFF, 0xFF, 0x68, 0x13, 0xFF, 0xFF, 0xFF];
+    assert_eq!(engine_speed.extract(&frame), Some(0x1368));
+    let mut data = [0xFF; 8];
+    engine_speed.insert(&mut data, 0x1368).unwrap();
+    assert_eq!(data, frame);
+
+    // Honda ENGINE_DATA: SG_ XMISSION_SPEED : 7|16@0+ (0.01,0) [0|250] "kph"
+    let speed = Signal::new(7, 16, Layout::Motorola);
+    let frame = [0x0B, 0xB8, 0, 0, 0, 0, 0, 0];
+    assert_eq!(speed.extract(&frame), Some(3000));
+    let mut data = [0; 8];
+    speed.insert(&mut data, 3000).unwrap();
+    assert_eq!(data, frame);
+
+    // Toyota STEER_ANGLE_SENSOR: SG_ STEER_ANGLE : 3|12@0- (1.5,0) [-1500|1500] "deg"
+    let angle = Signal::new(3, 12, Layout::Motorola);
+    let frame = [0xAF, 0xFE, 0, 0, 0, 0, 0, 0];
+    assert_eq!(angle.extract_signed(&frame), Some(-2));
+    let mut data = [0xA0, 0, 0, 0, 0, 0, 0, 0];
+    angle.insert(&mut data, -2i64 as u64).unwrap();
+    assert_eq!(data, frame);
+}
+
+#[test]
+fn signed_signals() {
+    assert_eq!(
+        Signal::new(0, 4, Layout::Intel).extract_signed(&[0x0F]),
+        Some(-1)
+    );
+    assert_eq!(
+        Signal::new(7, 8, Layout::Motorola).extract_signed(&[0x80]),
+        Some(-128)
+    );
+    assert_eq!(
+        Signal::new(6, 7, Layout::Motorola).extract_signed(&[0x3F]),
+        Some(63)
+    );
+    assert_eq!(
+        Signal::new(0, 64, Layout::Intel).extract_signed(&[0xFF; 8]),
+        Some(-1)
+    );
+}
+
+#[test]
+fn extract_matches_dbc_definition() {
+    for &layout in [Layout::Intel, Layout::Motorola].iter() {
+        for start_bit in 0..64 {
+            for length in 1..=64 {
+                let signal = Signal::new(start_bit, length, layout);
+                assert_eq!(
+                    signal.extract(&TEST_DATA),
+                    reference_extract(&signal, &TEST_DATA),
+                    "{:?}",
+                    signal
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn insert_touches_only_the_signal() {
+    for &layout in [Layout::Intel, Layout::Motorola].iter() {
+        for start_bit in 0..64 {
+            for length in 1..=64 {
+                let signal = Signal::new(start_bit, length, layout);
+                let mut data = TEST_DATA;
+                let value = !0x0123456789ABCDEFu64;
+                if signal.insert(&mut data, value).is_err() {
+                    assert_eq!(reference_extract(&signal, &TEST_DATA), None);
+                    assert_eq!(data, TEST_DATA);
+                    continue;
+                }
+
+                let mask = !0 >> (64 - length);
+                assert_eq!(signal.extract(&data), Some(value & mask));
+
+                // Every bit outside the signal keeps its value.
+                let mut touched = [0u8; 8];
+                for i in 0..length {
+                    let pos = reference_bit(&signal, i).unwrap();
+                    touched[(pos / 8) as usize] |= 1 << (pos % 8);
+                }
+                for ((after, before), touched) in
+                    data.iter().zip(TEST_DATA.iter()).zip(touched.iter())
+                {
+                    assert_eq!(after & !touched, before & !touched);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,385 @@
+//! Bit reversal of x86 vector registers, per lane or across the whole register.
+//!
+//! The bits of every byte are reversed with two `pshufb` nibble lookups, and the bytes are then
+//! reordered within each lane with one more `pshufb`. The `unsafe` functions can be called after
+//! detecting the CPU features at runtime. `SimdReverse` wraps them safely for `__m128i` when the
+//! crate is compiled with `ssse3` enabled, and for `__m256i` with `avx2` enabled.
+//!
+//! ```
+//! # #[cfg(target_arch = "x86_64")]
+//! # {
+//! use bit_reverse::simd;
+//! use std::arch::x86_64::*;
+//!
+//! if is_x86_feature_detected!("ssse3") {
+//!     let v = unsafe { simd::mm_reverse_epi32(_mm_set1_epi32(0x0000_0001)) };
+//!     let lanes: [u32; 4] = unsafe { std::mem::transmute(v) };
+//!     assert_eq!(lanes, [0x8000_0000; 4]);
+//! }
+//! # }
+//! ```
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+/// Bit reversal of vector registers. `swap_bits` reverses the whole register, and the other
+/// methods reverse each lane of the given width on its own.
+pub trait SimdReverse {
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the register in
+    /// bits.
+    fn swap_bits(self) -> Self;
+
+    /// Swaps the bits of each 8 bit lane.
+    fn swap_bits_u8(self) -> Self;
+
+    /// Swaps the bits of each 16 bit lane.
+    fn swap_bits_u16(self) -> Self;
+
+    /// Swaps the bits of each 32 bit lane.
+    fn swap_bits_u32(self) -> Self;
+
+    /// Swaps the bits of each 64 bit lane.
+    fn swap_bits_u64(self) -> Self;
+}
+
+/// Swaps the bits of each 8 bit lane.
+///
+/// # Safety
+/// The CPU must support SSSE3.
+#[target_feature(enable = "ssse3")]
+pub unsafe fn mm_reverse_epi8(v: __m128i) -> __m128i {
+    let mask = _mm_set1_epi8(0x0F);
+    let lo = _mm_and_si128(v, mask);
+    let hi = _mm_and_si128(_mm_srli_epi16(v, 4), mask);
+    // The reverse of the low nibble becomes the high nibble, and the other way around.
+    let lo_reversed = _mm_shuffle_epi8(
+        _mm_setr_epi8(
+            0x00, 0x80, 0x40, 0xC0, 0x20, 0xA0, 0x60, 0xE0, 0x10, 0x90, 0x50, 0xD0, 0x30, 0xB0,
+            0x70, 0xF0,
+        ),
+        lo,
+    );
+    let hi_reversed = _mm_shuffle_epi8(
+        _mm_setr_epi8(
+            0x0, 0x8, 0x4, 0xC, 0x2, 0xA, 0x6, 0xE, 0x1, 0x9, 0x5, 0xD, 0x3, 0xB, 0x7, 0xF,
+        ),
+        hi,
+    );
+    _mm_or_si128(lo_reversed, hi_reversed)
+}
+
+/// Swaps the bits of each 16 bit lane.
+///
+/// # Safety
+/// The CPU must support SSSE3.
+#[target_feature(enable = "ssse3")]
+pub unsafe fn mm_reverse_epi16(v: __m128i) -> __m128i {
+    _mm_shuffle_epi8(
+        mm_reverse_epi8(v),
+        _mm_setr_epi8(1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14),
+    )
+}
+
+/// Swaps the bits of each 32 bit lane.
+///
+/// # Safety
+/// The CPU must support SSSE3.
+#[target_feature(enable = "ssse3")]
+pub unsafe fn mm_reverse_epi32(v: __m128i) -> __m128i {
+    _mm_shuffle_epi8(
+        mm_reverse_epi8(v),
+        _mm_setr_epi8(3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12),
+    )
+}
+
+/// Swaps the bits of each 64 bit lane.
+///
+/// # Safety
+/// The CPU must support SSSE3.
+#[target_feature(enable = "ssse3")]
+pub unsafe fn mm_reverse_epi64(v: __m128i) -> __m128i {
+    _mm_shuffle_epi8(
+        mm_reverse_epi8(v),
+        _mm_setr_epi8(7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8),
+    )
+}
+
+/// Swaps the bits of the whole register.
+///
+/// # Safety
+/// The CPU must support SSSE3.
+#[target_feature(enable = "ssse3")]
+pub unsafe fn mm_reverse_si128(v: __m128i) -> __m128i {
+    _mm_shuffle_epi8(
+        mm_reverse_epi8(v),
+        _mm_setr_epi8(15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0),
+    )
+}
+
+/// Swaps the bits of each 8 bit lane.
+///
+/// # Safety
+/// The CPU must support AVX2.
+#[target_feature(enable = "avx2")]
+pub unsafe fn mm256_reverse_epi8(v: __m256i) -> __m256i {
+    let mask = _mm256_set1_epi8(0x0F);
+    let lo = _mm256_and_si256(v, mask);
+    let hi = _mm256_and_si256(_mm256_srli_epi16(v, 4), mask);
+    // The reverse of the low nibble becomes the high nibble, and the other way around. The
+    // shuffles look up within each 128 bit half, so the tables are repeated.
+    let lo_reversed = _mm256_shuffle_epi8(
+        _mm256_setr_epi8(
+            0x00, 0x80, 0x40, 0xC0, 0x20, 0xA0, 0x60, 0xE0, 0x10, 0x90, 0x50, 0xD0, 0x30, 0xB0,
+            0x70, 0xF0, 0x00, 0x80, 0x40, 0xC0, 0x20, 0xA0, 0x60, 0xE0, 0x10, 0x90, 0x50, 0xD0,
+            0x30, 0xB0, 0x70, 0xF0,
+        ),
+        lo,
+    );
+    let hi_reversed = _mm256_shuffle_epi8(
+        _mm256_setr_epi8(
+            0x0, 0x8, 0x4, 0xC, 0x2, 0xA, 0x6, 0xE, 0x1, 0x9, 0x5, 0xD, 0x3, 0xB, 0x7, 0xF, 0x0,
+            0x8, 0x4, 0xC, 0x2, 0xA, 0x6, 0xE, 0x1, 0x9, 0x5, 0xD, 0x3, 0xB, 0x7, 0xF,
+        ),
+        hi,
+    );
+    _mm256_or_si256(lo_reversed, hi_reversed)
+}
+
+/// Swaps the bits of each 16 bit lane.
+///
+/// # Safety
+/// The CPU must support AVX2.
+#[target_feature(enable = "avx2")]
+pub unsafe fn mm256_reverse_epi16(v: __m256i) -> __m256i {
+    _mm256_shuffle_epi8(
+        mm256_reverse_epi8(v),
+        _mm256_setr_epi8(
+            1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11,
+            10, 13, 12, 15, 14,
+        ),
+    )
+}
+
+/// Swaps the bits of each 32 bit lane.
+///
+/// # Safety
+/// The CPU must support AVX2.
+#[target_feature(enable = "avx2")]
+pub unsafe fn mm256_reverse_epi32(v: __m256i) -> __m256i {
+    _mm256_shuffle_epi8(
+        mm256_reverse_epi8(v),
+        _mm256_setr_epi8(
+            3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12, 3, 2, 1, 0, 7, 6, 5, 4, 11, 10,
+            9, 8, 15, 14, 13, 12,
+        ),
+    )
+}
+
+/// Swaps the bits of each 64 bit lane.
+///
+/// # Safety
+/// The CPU must support AVX2.
+#[target_feature(enable = "avx2")]
+pub unsafe fn mm256_reverse_epi64(v: __m256i) -> __m256i {
+    _mm256_shuffle_epi8(
+        mm256_reverse_epi8(v),
+        _mm256_setr_epi8(
+            7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 15, 14,
+            13, 12, 11, 10, 9, 8,
+        ),
+    )
+}
+
+/// Swaps the bits of the whole register.
+///
+/// # Safety
+/// The CPU must support AVX2.
+#[target_feature(enable = "avx2")]
+pub unsafe fn mm256_reverse_si256(v: __m256i) -> __m256i {
+    // Reverse each 128 bit half, then swap the halves.
+    let halves = _mm256_shuffle_epi8(
+        mm256_reverse_epi8(v),
+        _mm256_setr_epi8(
+            15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8, 7,
+            6, 5, 4, 3, 2, 1, 0,
+        ),
+    );
+    _mm256_permute4x64_epi64(halves, 0x4E)
+}
+
+#[cfg(target_feature = "ssse3")]
+impl SimdReverse for __m128i {
+    #[inline]
+    fn swap_bits(self) -> __m128i {
+        unsafe { mm_reverse_si128(self) }
+    }
+
+    #[inline]
+    fn swap_bits_u8(self) -> __m128i {
+        unsafe { mm_reverse_epi8(self) }
+    }
+
+    #[inline]
+    fn swap_bits_u16(self) -> __m128i {
+        unsafe { mm_reverse_epi16(self) }
+    }
+
+    #[inline]
+    fn swap_bits_u32(self) -> __m128i {
+        unsafe { mm_reverse_epi32(self) }
+    }
+
+    #[inline]
+    fn swap_bits_u64(self) -> __m128i {
+        unsafe { mm_reverse_epi64(self) }
+    }
+}
+
+#[cfg(target_feature = "avx2")]
+impl SimdReverse for __m256i {
+    #[inline]
+    fn swap_bits(self) -> __m256i {
+        unsafe { mm256_reverse_si256(self) }
+    }
+
+    #[inline]
+    fn swap_bits_u8(self) -> __m256i {
+        unsafe { mm256_reverse_epi8(self) }
+    }
+
+    #[inline]
+    fn swap_bits_u16(self) -> __m256i {
+        unsafe { mm256_reverse_epi16(self) }
+    }
+
+    #[inline]
+    fn swap_bits_u32(self) -> __m256i {
+        unsafe { mm256_reverse_epi32(self) }
+    }
+
+    #[inline]
+    fn swap_bits_u64(self) -> __m256i {
+        unsafe { mm256_reverse_epi64(self) }
+    }
+}
+
+// Reinterprets the register as lanes of type T, reverses them with `ParallelReverse`, and
+// reinterprets them back, for comparison against the vector code.
+#[cfg(all(test, feature = "use_std"))]
+fn scalar_lanes<V: Copy, T: ::ParallelReverse + Copy>(v: V, lanes: &mut [T]) -> V {
+    use core::{mem, ptr};
+
+    assert_eq!(mem::size_of_val(lanes), mem::size_of::<V>());
+    unsafe {
+        ptr::copy_nonoverlapping(&v as *const V as *const T, lanes.as_mut_ptr(), lanes.len());
+        for lane in lanes.iter_mut() {
+            *lane = ::ParallelReverse::swap_bits(*lane);
+        }
+        ptr::read_unaligned(lanes.as_ptr() as *const V)
+    }
+}
+
+#[cfg(all(test, any(feature = "use_std", target_feature = "ssse3")))]
+fn same<V>(a: V, b: V) -> bool {
+    use core::{mem, slice};
+
+    unsafe {
+        let a = slice::from_raw_parts(&a as *const V as *const u8, mem::size_of::<V>());
+        let b = slice::from_raw_parts(&b as *const V as *const u8, mem::size_of::<V>());
+        a == b
+    }
+}
+
+#[cfg(feature = "use_std")]
+#[test]
+fn ssse3_lanes_match_scalar() {
+    if !is_x86_feature_detected!("ssse3") {
+        return;
+    }
+    unsafe {
+        let v = _mm_setr_epi32(0x01234567, 0x89ABCDEFu32 as i32, 0x0F1E2D3C, 0x4B5A6978);
+        assert!(same(mm_reverse_epi8(v), scalar_lanes(v, &mut [0u8; 16])));
+        assert!(same(mm_reverse_epi16(v), scalar_lanes(v, &mut [0u16; 8])));
+        assert!(same(mm_reverse_epi32(v), scalar_lanes(v, &mut [0u32; 4])));
+        assert!(same(mm_reverse_epi64(v), scalar_lanes(v, &mut [0u64; 2])));
+
+        // The whole register is a 128 bit little-endian integer.
+        let mut halves = [0u64; 2];
+        scalar_lanes(v, &mut halves);
+        let whole = _mm_set_epi64x(halves[0] as i64, halves[1] as i64);
+        assert!(same(mm_reverse_si128(v), whole));
+    }
+}
+
+#[cfg(feature = "use_std")]
+#[test]
+fn avx2_lanes_match_scalar() {
+    if !is_x86_feature_detected!("avx2") {
+        return;
+    }
+    unsafe {
+        let v = _mm256_setr_epi32(
+            0x01234567,
+            0x89ABCDEFu32 as i32,
+            0x0F1E2D3C,
+            0x4B5A6978,
+            0x7F6E5D4C,
+            0x3B2A1908,
+            0x11223344,
+            0x55667788,
+        );
+        assert!(same(mm256_reverse_epi8(v), scalar_lanes(v, &mut [0u8; 32])));
+        assert!(same(
+            mm256_reverse_epi16(v),
+            scalar_lanes(v, &mut [0u16; 16])
+        ));
+        assert!(same(
+            mm256_reverse_epi32(v),
+            scalar_lanes(v, &mut [0u32; 8])
+        ));
+        assert!(same(
+            mm256_reverse_epi64(v),
+            scalar_lanes(v, &mut [0u64; 4])
+        ));
+
+        let mut quarters = [0u64; 4];
+        scalar_lanes(v, &mut quarters);
+        let whole = _mm256_set_epi64x(
+            quarters[0] as i64,
+            quarters[1] as i64,
+            quarters[2] as i64,
+            quarters[3] as i64,
+        );
+        assert!(same(mm256_reverse_si256(v), whole));
+    }
+}
+
+#[cfg(target_feature = "ssse3")]
+#[test]
+fn safe_m128i() {
+    unsafe {
+        let v = _mm_setr_epi32(1, 2, 3, 4);
+        assert!(same(v.swap_bits(), mm_reverse_si128(v)));
+        assert!(same(v.swap_bits_u8(), mm_reverse_epi8(v)));
+        assert!(same(v.swap_bits_u16(), mm_reverse_epi16(v)));
+        assert!(same(v.swap_bits_u32(), mm_reverse_epi32(v)));
+        assert!(same(v.swap_bits_u64(), mm_reverse_epi64(v)));
+        assert!(same(v.swap_bits().swap_bits(), v));
+    }
+}
+
+#[cfg(target_feature = "avx2")]
+#[test]
+fn safe_m256i() {
+    unsafe {
+        let v = _mm256_setr_epi32(1, 2, 3, 4, 5, 6, 7, 8);
+        assert!(same(v.swap_bits(), mm256_reverse_si256(v)));
+        assert!(same(v.swap_bits_u8(), mm256_reverse_epi8(v)));
+        assert!(same(v.swap_bits_u16(), mm256_reverse_epi16(v)));
+        assert!(same(v.swap_bits_u32(), mm256_reverse_epi32(v)));
+        assert!(same(v.swap_bits_u64(), mm256_reverse_epi64(v)));
+        assert!(same(v.swap_bits().swap_bits(), v));
+    }
+}
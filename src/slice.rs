@@ -0,0 +1,104 @@
+use BitOrder;
+use Endian;
+use ParallelReverse;
+use StreamBytes;
+
+/// Reverses the bits of every value in `values` in place, such that bit i of each value is now bit
+/// N-i, where N is the length of the T in bits.
+///
+/// ```
+/// use bit_reverse::swap_bits_slice;
+///
+/// let mut bytes = [0x01u8, 0xA0, 0xFF];
+/// swap_bits_slice(&mut bytes);
+/// assert_eq!(bytes, [0x80, 0x05, 0xFF]);
+/// ```
+#[inline]
+pub fn swap_bits_slice<T: ParallelReverse + Copy>(values: &mut [T]) {
+    for v in values.iter_mut() {
+        *v = v.swap_bits();
+    }
+}
+
+/// Reverses the bits of every `T` packed into `buf` in byte order `E`, in place. The buffer needs
+/// no particular alignment.
+///
+/// ```
+/// use bit_reverse::{swap_bits_in_buffer, BigEndian};
+///
+/// let mut buf = [0x12, 0x34, 0x56, 0x78, 0x00, 0x00, 0x00, 0x01];
+/// swap_bits_in_buffer::<u32, BigEndian>(&mut buf);
+/// assert_eq!(buf, [0x1E, 0x6A, 0x2C, 0x48, 0x80, 0x00, 0x00, 0x00]);
+/// ```
+///
+/// # Panics
+/// Panics if the length of `buf` isn't a multiple of the size of `T`.
+pub fn swap_bits_in_buffer<T, E: Endian>(buf: &mut [u8])
+where
+    T: StreamBytes + ParallelReverse,
+    T::Bytes: AsRef<[u8]> + AsMut<[u8]> + Default,
+{
+    let size = ::core::mem::size_of::<T>();
+    assert!(
+        buf.len().is_multiple_of(size),
+        "buffer length must be a multiple of the value size"
+    );
+
+    for chunk in buf.chunks_exact_mut(size) {
+        let mut bytes = T::Bytes::default();
+        bytes.as_mut().copy_from_slice(chunk);
+        let value = T::from_stream_bytes::<E>(bytes, BitOrder::MsbFirst).swap_bits();
+        chunk.copy_from_slice(value.to_stream_bytes::<E>(BitOrder::MsbFirst).as_ref());
+    }
+}
+
+#[test]
+fn reverse_u16_slice() {
+    let mut words = [0xABCDu16, 0x0001, 0x0000];
+    swap_bits_slice(&mut words);
+    assert_eq!(words, [0xB3D5, 0x8000, 0x0000]);
+}
+
+#[test]
+fn reverse_empty_slice() {
+    let mut empty: [u32; 0] = [];
+    swap_bits_slice(&mut empty);
+}
+
+#[cfg(test)]
+use {BigEndian, LittleEndian};
+
+#[test]
+fn buffer_matches_values() {
+    let values = [0x12345678u32, 0xDEADBEEF, 0x00000001, 0xFFFF0000];
+    let mut be = [0u8; 17];
+    let mut le = [0u8; 17];
+    // Start at an odd offset so the values are unaligned.
+    for (i, v) in values.iter().enumerate() {
+        be[1 + 4 * i..5 + 4 * i].copy_from_slice(&v.to_be_bytes());
+        le[1 + 4 * i..5 + 4 * i].copy_from_slice(&v.to_le_bytes());
+    }
+    swap_bits_in_buffer::<u32, BigEndian>(&mut be[1..]);
+    swap_bits_in_buffer::<u32, LittleEndian>(&mut le[1..]);
+
+    for (i, v) in values.iter().enumerate() {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(&be[1 + 4 * i..5 + 4 * i]);
+        assert_eq!(
+            u32::from_stream_bytes::<BigEndian>(bytes, BitOrder::MsbFirst),
+            v.swap_bits()
+        );
+        bytes.copy_from_slice(&le[1 + 4 * i..5 + 4 * i]);
+        assert_eq!(
+            u32::from_stream_bytes::<LittleEndian>(bytes, BitOrder::MsbFirst),
+            v.swap_bits()
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn buffer_with_partial_value() {
+    let mut buf = [0u8; 6];
+    swap_bits_in_buffer::<u32, BigEndian>(&mut buf);
+}
@@ -0,0 +1,250 @@
+use BitwiseReverse;
+use ConstTimeReverse;
+use GfniReverse;
+use LookupReverse;
+use MultiplyReverse;
+use NativeReverse;
+use NibbleLookupReverse;
+use NibbleRegisterReverse;
+use ParallelReverse;
+use WideLookupReverse;
+
+/// A bit reversal algorithm selected by type, so generic code can be written once and callers
+/// can pick the algorithm.
+pub trait Strategy<T> {
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
+    fn swap_bits(value: T) -> T;
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    fn swap_low_bits(value: T, bits: u32) -> T;
+}
+
+/// Selects `BitwiseReverse`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Bitwise {}
+
+/// Selects `LookupReverse`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Lookup {}
+
+/// Selects `ParallelReverse`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Parallel {}
+
+/// Selects `ConstTimeReverse`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConstTime {}
+
+/// Selects `NativeReverse`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Native {}
+
+/// Selects `NibbleLookupReverse`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NibbleLookup {}
+
+/// Selects `NibbleRegisterReverse`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NibbleRegister {}
+
+/// Selects `WideLookupReverse`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WideLookup {}
+
+/// Selects `MultiplyReverse`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Multiply {}
+
+/// Selects `GfniReverse`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Gfni {}
+
+macro_rules! doit_strategy {
+    ($($Strategy:ident => $Algo:ident),*) => ($(
+        impl<T: $Algo> Strategy<T> for $Strategy {
+            #[inline]
+            fn swap_bits(value: T) -> T {
+                $Algo::swap_bits(value)
+            }
+
+            #[inline]
+            fn swap_low_bits(value: T, bits: u32) -> T {
+                $Algo::swap_low_bits(value, bits)
+            }
+        }
+    )*)
+}
+
+doit_strategy!(
+    Bitwise => BitwiseReverse,
+    Lookup => LookupReverse,
+    Parallel => ParallelReverse,
+    Native => NativeReverse,
+    ConstTime => ConstTimeReverse,
+    NibbleLookup => NibbleLookupReverse,
+    NibbleRegister => NibbleRegisterReverse,
+    WideLookup => WideLookupReverse,
+    Multiply => MultiplyReverse,
+    Gfni => GfniReverse
+);
+
+/// Bit reversal with the algorithm chosen by a type parameter. Unlike importing the algorithm
+/// traits together it doesn't make `swap_bits` ambiguous.
+///
+/// Only the trait of the `DefaultStrategy` algorithm is required, so a type that implements it
+/// can be passed to `reverse`. Each other algorithm is bounded where it is used, through the
+/// `Strategy<Self>` bound on `A`.
+///
+/// ```
+/// use bit_reverse::{BitReverse, Lookup, Parallel, Strategy};
+///
+/// fn reflect<T: BitReverse, A: Strategy<T>>(v: T) -> T {
+///     v.reverse_bits_with::<A>()
+/// }
+///
+/// assert_eq!(reflect::<u8, Lookup>(0xA0), 0x05);
+/// assert_eq!(0xA0u8.reverse_bits_with::<Parallel>(), 0x05);
+/// ```
+pub trait BitReverse: DefaultReverse + Sized {
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits,
+    /// using algorithm `A`.
+    #[inline]
+    fn reverse_bits_with<A: Strategy<Self>>(self) -> Self {
+        A::swap_bits(self)
+    }
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i, using algorithm `A`.
+    /// The remaining high bits are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    #[inline]
+    fn reverse_low_bits_with<A: Strategy<Self>>(self, bits: u32) -> Self {
+        A::swap_low_bits(self, bits)
+    }
+}
+
+impl<T: DefaultReverse> BitReverse for T {}
+
+#[cfg(feature = "default-bitwise")]
+use BitwiseReverse as DefaultReverse;
+#[cfg(feature = "default-bitwise")]
+type Selected = Bitwise;
+#[cfg(all(feature = "default-lookup", not(feature = "default-bitwise")))]
+use LookupReverse as DefaultReverse;
+#[cfg(all(feature = "default-lookup", not(feature = "default-bitwise")))]
+type Selected = Lookup;
+#[cfg(not(any(feature = "default-bitwise", feature = "default-lookup")))]
+use ParallelReverse as DefaultReverse;
+#[cfg(not(any(feature = "default-bitwise", feature = "default-lookup")))]
+type Selected = Parallel;
+
+/// The algorithm behind `reverse` and `reverse_low_bits`, chosen with the `default-bitwise` and
+/// `default-lookup` features.
+///
+/// When both are enabled the smaller algorithm wins, so a binary that selects `BitwiseReverse`
+/// for code size keeps it even if a dependency selects `LookupReverse`. Without either feature
+/// `ParallelReverse` is used. `default-parallel` doesn't change anything, since parallel is
+/// already the fallback; it exists so a crate can state its choice explicitly, and it loses to
+/// the other two.
+pub type DefaultStrategy = Selected;
+
+/// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits, using
+/// the `DefaultStrategy` algorithm.
+///
+/// ```
+/// assert_eq!(bit_reverse::reverse(0xA0u8), 0x05u8);
+/// ```
+#[inline]
+pub fn reverse<T: BitReverse>(value: T) -> T {
+    value.reverse_bits_with::<DefaultStrategy>()
+}
+
+/// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i, using the
+/// `DefaultStrategy` algorithm. The remaining high bits are cleared.
+///
+/// # Panics
+/// Panics if `bits` is larger than the length of the T in bits.
+#[inline]
+pub fn reverse_low_bits<T: BitReverse>(value: T, bits: u32) -> T {
+    value.reverse_low_bits_with::<DefaultStrategy>(bits)
+}
+
+#[cfg(test)]
+fn every_algorithm<T>(v: T) -> [T; 10]
+where
+    T: BitwiseReverse
+        + LookupReverse
+        + ParallelReverse
+        + NativeReverse
+        + ConstTimeReverse
+        + NibbleLookupReverse
+        + NibbleRegisterReverse
+        + WideLookupReverse
+        + MultiplyReverse
+        + GfniReverse
+        + BitReverse
+        + Copy,
+{
+    [
+        v.reverse_bits_with::<Bitwise>(),
+        v.reverse_bits_with::<Lookup>(),
+        v.reverse_bits_with::<Parallel>(),
+        v.reverse_bits_with::<Native>(),
+        v.reverse_bits_with::<ConstTime>(),
+        v.reverse_bits_with::<NibbleLookup>(),
+        v.reverse_bits_with::<NibbleRegister>(),
+        v.reverse_bits_with::<WideLookup>(),
+        v.reverse_bits_with::<Multiply>(),
+        v.reverse_bits_with::<Gfni>(),
+    ]
+}
+
+#[test]
+fn strategies_agree() {
+    assert_eq!(every_algorithm(0xABu8), [0xD5; 10]);
+    assert_eq!(every_algorithm(0xABCD2345u32), [0xA2C4B3D5; 10]);
+    assert_eq!(
+        every_algorithm(0x0123456789ABCDEFi64),
+        [0xF7B3D591E6A2C480; 10]
+    );
+}
+
+#[test]
+fn low_bits_with_strategy() {
+    assert_eq!(0b1011u16.reverse_low_bits_with::<Bitwise>(4), 0b1101);
+    assert_eq!(0b1011u16.reverse_low_bits_with::<Lookup>(4), 0b1101);
+    assert_eq!(0b1011u16.reverse_low_bits_with::<Parallel>(4), 0b1101);
+    assert_eq!(0b1011u16.reverse_low_bits_with::<Native>(4), 0b1101);
+    assert_eq!(0b1011u16.reverse_low_bits_with::<ConstTime>(4), 0b1101);
+    assert_eq!(0b1011u16.reverse_low_bits_with::<NibbleLookup>(4), 0b1101);
+    assert_eq!(0b1011u16.reverse_low_bits_with::<NibbleRegister>(4), 0b1101);
+    assert_eq!(0b1011u16.reverse_low_bits_with::<WideLookup>(4), 0b1101);
+    assert_eq!(0b1011u16.reverse_low_bits_with::<Multiply>(4), 0b1101);
+    assert_eq!(0b1011u16.reverse_low_bits_with::<Gfni>(4), 0b1101);
+}
+
+#[test]
+fn default_strategy_follows_features() {
+    use core::any::TypeId;
+
+    let expected = if cfg!(feature = "default-bitwise") {
+        TypeId::of::<Bitwise>()
+    } else if cfg!(feature = "default-lookup") {
+        TypeId::of::<Lookup>()
+    } else {
+        TypeId::of::<Parallel>()
+    };
+    assert_eq!(TypeId::of::<DefaultStrategy>(), expected);
+}
+
+#[test]
+fn default_entry_points() {
+    assert_eq!(reverse(0xABCD2345u32), 0xA2C4B3D5);
+    assert_eq!(reverse(-1i16), -1);
+    assert_eq!(reverse_low_bits(0b1011u8, 4), 0b1101);
+}
@@ -0,0 +1,167 @@
+use BitOrder;
+use Endian;
+use ParallelReverse;
+
+/// Converts integers to and from the bytes of a bit stream, choosing both the byte order and the
+/// order of the bits within each byte.
+///
+/// The bytes are meant to be read as a stream one byte at a time, taking the bits of each byte
+/// MSB-first the way shift registers and SPI peripherals do. With `BitOrder::LsbFirst` the bits
+/// of every byte are reversed, so they come out of such a reader least significant bit first.
+///
+/// ```
+/// use bit_reverse::StreamBytes;
+///
+/// // The value's least significant bit is sent first.
+/// assert_eq!(0x0001u16.to_lsb_first_bytes(), [0x80, 0x00]);
+/// assert_eq!(u16::from_lsb_first_bytes([0x80, 0x00]), 0x0001);
+///
+/// // The value's most significant bit is sent first, which is the big-endian layout.
+/// assert_eq!(0x0001u16.to_msb_first_bytes(), [0x00, 0x01]);
+/// ```
+pub trait StreamBytes: Sized {
+    /// The byte array holding every byte of the integer.
+    type Bytes;
+
+    /// Returns the bytes in byte order `E`, with the bits of every byte in `order`.
+    fn to_stream_bytes<E: Endian>(self, order: BitOrder) -> Self::Bytes;
+
+    /// Creates an integer from bytes in byte order `E`, with the bits of every byte in `order`.
+    fn from_stream_bytes<E: Endian>(bytes: Self::Bytes, order: BitOrder) -> Self;
+
+    /// Returns the bytes that send the integer least significant bit first. The bytes are in
+    /// little-endian order with the bits of every byte reversed.
+    fn to_lsb_first_bytes(self) -> Self::Bytes;
+
+    /// Creates an integer from bytes that send it least significant bit first.
+    fn from_lsb_first_bytes(bytes: Self::Bytes) -> Self;
+
+    /// Returns the bytes that send the integer most significant bit first, which are its
+    /// big-endian bytes.
+    fn to_msb_first_bytes(self) -> Self::Bytes;
+
+    /// Creates an integer from bytes that send it most significant bit first.
+    fn from_msb_first_bytes(bytes: Self::Bytes) -> Self;
+}
+
+macro_rules! doit_stream_bytes { ($($ty:ty),*) => ($(
+    impl StreamBytes for $ty {
+        type Bytes = [u8; ::core::mem::size_of::<$ty>()];
+
+        #[inline]
+        fn to_stream_bytes<E: Endian>(self, order: BitOrder) -> Self::Bytes {
+            // Reversing every bit and then every byte leaves the bytes in place with their bits
+            // reversed.
+            let v = match order {
+                BitOrder::LsbFirst => ParallelReverse::swap_bits(self).swap_bytes(),
+                BitOrder::MsbFirst => self,
+            };
+            if E::IS_BIG_ENDIAN {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        }
+
+        #[inline]
+        fn from_stream_bytes<E: Endian>(bytes: Self::Bytes, order: BitOrder) -> $ty {
+            let v = if E::IS_BIG_ENDIAN {
+                <$ty>::from_be_bytes(bytes)
+            } else {
+                <$ty>::from_le_bytes(bytes)
+            };
+            match order {
+                BitOrder::LsbFirst => ParallelReverse::swap_bits(v).swap_bytes(),
+                BitOrder::MsbFirst => v,
+            }
+        }
+
+        #[inline]
+        fn to_lsb_first_bytes(self) -> Self::Bytes {
+            ParallelReverse::swap_bits(self).to_be_bytes()
+        }
+
+        #[inline]
+        fn from_lsb_first_bytes(bytes: Self::Bytes) -> $ty {
+            ParallelReverse::swap_bits(<$ty>::from_be_bytes(bytes))
+        }
+
+        #[inline]
+        fn to_msb_first_bytes(self) -> Self::Bytes {
+            self.to_be_bytes()
+        }
+
+        #[inline]
+        fn from_msb_first_bytes(bytes: Self::Bytes) -> $ty {
+            <$ty>::from_be_bytes(bytes)
+        }
+    })*)
+}
+
+doit_stream_bytes!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+#[cfg(feature = "u128")]
+doit_stream_bytes!(u128, i128);
+
+#[cfg(test)]
+use {BigEndian, LittleEndian};
+
+#[test]
+fn four_combinations_u32() {
+    let v = 0x12345678u32;
+    assert_eq!(
+        v.to_stream_bytes::<BigEndian>(BitOrder::MsbFirst),
+        [0x12, 0x34, 0x56, 0x78]
+    );
+    assert_eq!(
+        v.to_stream_bytes::<LittleEndian>(BitOrder::MsbFirst),
+        [0x78, 0x56, 0x34, 0x12]
+    );
+    assert_eq!(
+        v.to_stream_bytes::<BigEndian>(BitOrder::LsbFirst),
+        [0x48, 0x2C, 0x6A, 0x1E]
+    );
+    assert_eq!(
+        v.to_stream_bytes::<LittleEndian>(BitOrder::LsbFirst),
+        [0x1E, 0x6A, 0x2C, 0x48]
+    );
+}
+
+#[test]
+fn named_conversions_match_combinations() {
+    let v = 0x0123456789ABCDEFu64;
+    assert_eq!(
+        v.to_lsb_first_bytes(),
+        v.to_stream_bytes::<LittleEndian>(BitOrder::LsbFirst)
+    );
+    assert_eq!(
+        v.to_msb_first_bytes(),
+        v.to_stream_bytes::<BigEndian>(BitOrder::MsbFirst)
+    );
+    assert_eq!(u64::from_lsb_first_bytes(v.to_lsb_first_bytes()), v);
+    assert_eq!(u64::from_msb_first_bytes(v.to_msb_first_bytes()), v);
+}
+
+#[test]
+fn round_trip_signed() {
+    for &order in [BitOrder::LsbFirst, BitOrder::MsbFirst].iter() {
+        let v = -0x1234i16;
+        let be = v.to_stream_bytes::<BigEndian>(order);
+        let le = v.to_stream_bytes::<LittleEndian>(order);
+        assert_eq!(i16::from_stream_bytes::<BigEndian>(be, order), v);
+        assert_eq!(i16::from_stream_bytes::<LittleEndian>(le, order), v);
+    }
+    assert_eq!((-1i8).to_lsb_first_bytes(), [0xFF]);
+    assert_eq!(1i8.to_lsb_first_bytes(), [0x80]);
+}
+
+#[test]
+fn lsb_first_bytes_read_msb_first() {
+    use bitio::BitReader;
+
+    // An MSB-first reader sees the value's bits from least to most significant.
+    let bytes = 0xABCDu16.to_lsb_first_bytes();
+    let mut reader = BitReader::new(&bytes, BitOrder::MsbFirst);
+    for i in 0..16 {
+        assert_eq!(reader.read_bit(), Some((0xABCD >> i) & 1 != 0));
+    }
+}
@@ -0,0 +1,113 @@
+/// Computes bit reversal by using a 65536 entry lookup table to translate 16 bits at a time into
+/// their reverse. This halves the number of lookups `LookupReverse` needs for wide types, at the
+/// cost of a 128 KiB table.
+///
+/// # Timing
+/// Not constant time. The table is indexed by the value, so the running time can leak the value
+/// through the cache.
+pub trait WideLookupReverse {
+    /// Swaps the bits such that bit i is now bit N-i, where N is the length of the T in bits.
+    fn swap_bits(self) -> Self;
+
+    /// Swaps the lowest `bits` bits such that bit i is now bit `bits`-1-i. The remaining high bits
+    /// are cleared.
+    ///
+    /// # Panics
+    /// Panics if `bits` is larger than the length of the T in bits.
+    fn swap_low_bits(self, bits: u32) -> Self;
+}
+
+// Generated at compile time. It is a static rather than a const so there is only ever one copy.
+static WIDE_REVERSE_LOOKUP: [u16; 65536] = ::lookup::reverse_table_u16::<65536>();
+
+impl WideLookupReverse for u8 {
+    #[inline]
+    fn swap_bits(self) -> u8 {
+        (WIDE_REVERSE_LOOKUP[self as usize] >> 8) as u8
+    }
+
+    swap_low_bits!(WideLookupReverse, u8);
+}
+
+impl WideLookupReverse for u16 {
+    #[inline]
+    fn swap_bits(self) -> u16 {
+        WIDE_REVERSE_LOOKUP[self as usize]
+    }
+
+    swap_low_bits!(WideLookupReverse, u16);
+}
+
+impl WideLookupReverse for u32 {
+    #[inline]
+    fn swap_bits(self) -> u32 {
+        (WIDE_REVERSE_LOOKUP[self as u16 as usize] as u32) << 16
+            | (WIDE_REVERSE_LOOKUP[(self >> 16) as u16 as usize] as u32)
+    }
+
+    swap_low_bits!(WideLookupReverse, u32);
+}
+
+impl WideLookupReverse for u64 {
+    #[inline]
+    fn swap_bits(self) -> u64 {
+        (WIDE_REVERSE_LOOKUP[self as u16 as usize] as u64) << 48
+            | (WIDE_REVERSE_LOOKUP[(self >> 16) as u16 as usize] as u64) << 32
+            | (WIDE_REVERSE_LOOKUP[(self >> 32) as u16 as usize] as u64) << 16
+            | (WIDE_REVERSE_LOOKUP[(self >> 48) as u16 as usize] as u64)
+    }
+
+    swap_low_bits!(WideLookupReverse, u64);
+}
+
+#[cfg(feature = "u128")]
+impl WideLookupReverse for u128 {
+    #[inline]
+    fn swap_bits(self) -> u128 {
+        (WideLookupReverse::swap_bits(self as u64) as u128) << 64
+            | (WideLookupReverse::swap_bits((self >> 64) as u64) as u128)
+    }
+
+    swap_low_bits!(WideLookupReverse, u128);
+}
+
+impl WideLookupReverse for usize {
+    #[inline]
+    #[cfg(target_pointer_width = "8")]
+    fn swap_bits(self) -> usize {
+        WideLookupReverse::swap_bits(self as u8) as usize
+    }
+
+    #[inline]
+    #[cfg(target_pointer_width = "16")]
+    fn swap_bits(self) -> usize {
+        WideLookupReverse::swap_bits(self as u16) as usize
+    }
+
+    #[inline]
+    #[cfg(target_pointer_width = "32")]
+    fn swap_bits(self) -> usize {
+        WideLookupReverse::swap_bits(self as u32) as usize
+    }
+
+    #[inline]
+    #[cfg(target_pointer_width = "64")]
+    fn swap_bits(self) -> usize {
+        WideLookupReverse::swap_bits(self as u64) as usize
+    }
+
+    swap_low_bits!(WideLookupReverse, usize);
+}
+
+doit_signed!(WideLookupReverse);
+test_suite!();
+
+#[test]
+fn wide_table() {
+    for i in 0..=0xFFFFu16 {
+        assert_eq!(
+            WIDE_REVERSE_LOOKUP[i as usize],
+            ::ParallelReverse::swap_bits(i)
+        );
+    }
+}